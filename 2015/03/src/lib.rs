@@ -1,13 +1,7 @@
-use common::{Answer, BooleanGrid, Coordinates};
+use common::{Answer, BooleanGrid, Coordinates, Direction};
 
 fn move_house(c: char, pos: &mut Coordinates<i32>) {
-    match c {
-        '^' => pos.move_up(),
-        '>' => pos.move_right(),
-        'v' => pos.move_down(),
-        '<' => pos.move_left(),
-        _ => unreachable!(),
-    };
+    pos.move_next(Direction::try_from(c).expect("Invalid move"));
 }
 
 pub fn step1(s: &str) -> Answer {
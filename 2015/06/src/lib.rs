@@ -1,9 +1,9 @@
-use common::{Answer, Coordinates, Grid};
+use common::{Answer, BitGrid, Coordinates, Grid};
 use regex::Regex;
 use std::sync::LazyLock;
 
 type IntType = u16;
-type Lights = Grid<IntType, bool>;
+type Lights = BitGrid<IntType>;
 type DimmableLights = Grid<IntType, i32>;
 type Coords = Coordinates<IntType>;
 
@@ -36,37 +36,16 @@ fn parse(s: &str) -> impl Iterator<Item = Instruction> + '_ {
     })
 }
 
-#[inline]
-fn turn_on(lights: &mut Lights, pos: Coords) {
-    lights.store(pos, true);
-}
-
-#[inline]
-fn turn_off(lights: &mut Lights, pos: Coords) {
-    lights.store(pos, false);
-}
-
-#[inline]
-fn toggle(lights: &mut Lights, pos: Coords) {
-    let value = *lights.get(&pos).unwrap_or(&false);
-    lights.store(pos, !value);
-}
-
-#[inline]
-fn count_lights(lights: &Lights) -> usize {
-    lights.iter().filter(|(_, b)| **b).count()
-}
-
 pub fn step1(s: &str) -> Answer {
-    let mut lights = Lights::new();
+    let mut lights = Lights::new(Coordinates::new(0, 0), Coordinates::new(999, 999));
 
     parse(s).for_each(|i| match i {
-        Instruction::On(a, b) => a.range_inclusive(b).for_each(|p| turn_on(&mut lights, p)),
-        Instruction::Off(a, b) => a.range_inclusive(b).for_each(|p| turn_off(&mut lights, p)),
-        Instruction::Toggle(a, b) => a.range_inclusive(b).for_each(|p| toggle(&mut lights, p)),
+        Instruction::On(a, b) => lights.mark_rect(a, b),
+        Instruction::Off(a, b) => lights.remove_rect(a, b),
+        Instruction::Toggle(a, b) => lights.toggle_rect(a, b),
     });
 
-    count_lights(&lights).into()
+    lights.len().into()
 }
 
 #[inline]
@@ -147,6 +126,22 @@ mod test {
         assert_eq!(sum_brightness(&lights), 4);
     }
 
+    #[test]
+    fn step1_handles_examples() {
+        assert_eq!(
+            step1("turn on 0,0 through 999,999"),
+            Answer::Unsigned(1_000_000)
+        );
+        assert_eq!(
+            step1("turn on 0,0 through 999,999\ntoggle 0,0 through 999,0"),
+            Answer::Unsigned(999_000)
+        );
+        assert_eq!(
+            step1("toggle 0,0 through 999,0\nturn off 499,499 through 500,500"),
+            Answer::Unsigned(1000)
+        );
+    }
+
     #[test]
     fn step2_handles_first_example() {
         let answer = step2("turn on 0,0 through 0,0");
@@ -1,67 +1,29 @@
-use common::{Answer, BooleanGrid, Coordinates};
+use common::{Answer, BirthSurvival, BitGrid, Coordinates};
 
 type IntType = i8;
-type Lights = BooleanGrid<IntType>;
-type Coords = Coordinates<IntType>;
-
-fn parse(s: &str) -> Lights {
-    let mut lights = Lights::new();
-
-    s.lines().enumerate().for_each(|(y, l)| {
-        l.chars()
-            .enumerate()
-            .filter(|(_, c)| *c == '#')
-            .for_each(|(x, _)| lights.mark((x as u8, y as u8).into()))
-    });
-
-    lights
-}
-
-fn update(lights: &mut Lights, broken: bool) {
-    if broken {
-        lights.mark((lights.width() - 1, lights.height() - 1).into());
-        lights.mark((0, lights.height() - 1).into());
-        lights.mark((lights.width() - 1, 0).into());
-        lights.mark((0, 0).into());
-    }
-
-    let start_state = lights.clone();
-
-    (0..start_state.width()).for_each(|x| {
-        (0..start_state.height()).for_each(|y| {
-            let xy = (x, y);
-            if broken
-                && (xy == (0, 0)
-                    || xy == (lights.width() - 1, lights.height() - 1)
-                    || xy == (0, lights.height() - 1)
-                    || xy == (lights.height() - 1, 0))
-            {
-                return;
-            }
-
-            let pos = Coords::from((x, y));
-            let light = lights.contains(&pos);
-
-            let count = pos
-                .neighbors_8()
-                .into_iter()
-                .filter(|pos| start_state.contains(pos))
-                .count();
-
-            if light && count != 2 && count != 3 {
-                lights.remove(&pos);
-            } else if !light && count == 3 {
-                lights.mark(pos);
-            }
-        })
-    });
-}
+type Lights = BitGrid<IntType>;
 
 fn animate(s: &str, iterations: usize, broken: bool) -> usize {
-    let mut lights = parse(s);
+    let mut lights = Lights::parse(s, |c| c == '#');
+    let (min, max) = lights.bounding_box();
+    let corners = [
+        min,
+        Coordinates::new(max.x(), min.y()),
+        Coordinates::new(min.x(), max.y()),
+        max,
+    ];
+    let pin = |lights: &mut Lights| {
+        if broken {
+            corners.iter().for_each(|&pos| {
+                lights.mark(pos);
+            });
+        }
+    };
 
+    pin(&mut lights);
     for _ in 0..iterations {
-        update(&mut lights, broken);
+        lights = lights.step(&BirthSurvival::conway());
+        pin(&mut lights);
     }
 
     lights.len()
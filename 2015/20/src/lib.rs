@@ -1,50 +1,22 @@
-use common::Answer;
+use common::{
+    Answer,
+    sieve::{divisor_sums, divisor_sums_limited},
+};
+
+const PRESENTS: u64 = 33100000;
+
+/// First house receiving at least `PRESENTS` presents, given the presents
+/// each house receives per unit of divisor sum.
+fn first_house(sums: &[u64], per_elf: u64) -> usize {
+    sums.iter()
+        .position(|&sum| sum * per_elf >= PRESENTS)
+        .unwrap()
+}
 
 pub fn step1(_: &str) -> Answer {
-    let n = 33100000;
-
-    let max = n / 10;
-    let mut houses = vec![0; max];
-
-    for i in 1..max {
-        for j in (i..max).step_by(i) {
-            houses[j] += i as u32;
-        }
-    }
-
-    houses
-        .iter()
-        .enumerate()
-        .find(|(_, v)| (**v) as usize >= max)
-        .map(|(k, _)| k)
-        .unwrap()
-        .into()
+    first_house(&divisor_sums((PRESENTS / 10) as usize), 10).into()
 }
 
 pub fn step2(_: &str) -> Answer {
-    let n = 33100000;
-
-    let max = n / 11;
-    let mut houses = vec![0; max / 3];
-
-    for i in 1..houses.len() {
-        let mut count = 0;
-
-        for j in (i..houses.len()).step_by(i) {
-            houses[j] += i as u32;
-            count += 1;
-
-            if count == 50 {
-                break;
-            }
-        }
-    }
-
-    houses
-        .iter()
-        .enumerate()
-        .find(|(_, v)| **v >= max as u32)
-        .map(|(k, _)| k)
-        .unwrap()
-        .into()
+    first_house(&divisor_sums_limited((PRESENTS / 11) as usize, 50), 11).into()
 }
@@ -1,165 +1,66 @@
-use common::Answer;
+use common::{Answer, BoundedGrid, Coordinates, Direction};
 
-fn is_visible_left(tree_map: &[i8], idx: usize, width: usize) -> bool {
-    for i in ((idx - (idx % width))..(idx)).rev() {
-        if tree_map[i] >= tree_map[idx] {
-            return false;
-        }
-    }
+type Forest = BoundedGrid<u8, u8>;
 
-    true
+fn parse_tree_map(s: &str) -> Forest {
+    Forest::parse(s, |c| {
+        Some(c.to_digit(10).expect("Couldn't parse tree into size") as u8)
+    })
 }
 
-fn is_visible_right(tree_map: &[i8], idx: usize, width: usize) -> bool {
-    for i in (idx + 1)..(idx + (width - (idx % width))) {
-        if tree_map[i] >= tree_map[idx] {
-            return false;
-        }
-    }
-
-    true
+fn is_visible(forest: &Forest, pos: Coordinates<u8>, height: u8) -> bool {
+    Direction::ALL.into_iter().any(|dir| {
+        forest
+            .ray(pos, dir)
+            .all(|tree| forest.get(&tree).is_some_and(|&h| h < height))
+    })
 }
 
-fn is_visible_top(tree_map: &[i8], idx: usize, width: usize) -> bool {
-    let mut i = idx;
-
-    while i >= width {
-        i -= width;
-        if tree_map[i] >= tree_map[idx] {
-            return false;
-        }
-    }
-
-    true
+fn calculate_scenic_score(forest: &Forest, pos: Coordinates<u8>, height: u8) -> usize {
+    Direction::ALL
+        .into_iter()
+        .map(|dir| forest.line_of_sight(pos, dir, |&h| h >= height).count())
+        .product()
 }
 
-fn is_visible_bottom(tree_map: &[i8], idx: usize, width: usize, height: usize) -> bool {
-    let mut i = idx;
-
-    while i < width * (height - 1) {
-        i += width;
-        if tree_map[i] >= tree_map[idx] {
-            return false;
-        }
-    }
-
-    true
-}
+pub fn step1(s: &str) -> Answer {
+    let forest = parse_tree_map(s);
 
-fn is_visible(tree_map: &[i8], idx: usize, width: usize, height: usize) -> bool {
-    is_visible_left(tree_map, idx, width)
-        || is_visible_right(tree_map, idx, width)
-        || is_visible_top(tree_map, idx, width)
-        || is_visible_bottom(tree_map, idx, width, height)
+    forest
+        .iter()
+        .filter(|&(&pos, &height)| is_visible(&forest, pos, height))
+        .count()
+        .into()
 }
 
-fn scenic_score_left(tree_map: &[i8], idx: usize, width: usize) -> usize {
-    let mut score = 0;
-
-    for i in ((idx - (idx % width))..(idx)).rev() {
-        score += 1;
-        if tree_map[i] >= tree_map[idx] {
-            break;
-        }
-    }
+pub fn step2(s: &str) -> Answer {
+    let forest = parse_tree_map(s);
 
-    score
+    forest
+        .iter()
+        .map(|(&pos, &height)| calculate_scenic_score(&forest, pos, height))
+        .max()
+        .expect("Couldn't find a maximum scenic score")
+        .into()
 }
 
-fn scenic_score_right(tree_map: &[i8], idx: usize, width: usize) -> usize {
-    let mut score = 0;
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    for i in (idx + 1)..(idx + (width - (idx % width))) {
-        score += 1;
-        if tree_map[i] >= tree_map[idx] {
-            break;
-        }
-    }
+    const INPUT: &str = r#"30373
+25512
+65332
+33549
+35390"#;
 
-    score
-}
-
-fn scenic_score_top(tree_map: &[i8], idx: usize, width: usize) -> usize {
-    let mut i = idx;
-    let mut score = 0;
-
-    while i >= width {
-        score += 1;
-        i -= width;
-        if tree_map[i] >= tree_map[idx] {
-            break;
-        }
+    #[test]
+    fn step1_finds_correct_answer() {
+        assert_eq!(step1(INPUT), Answer::Unsigned(21));
     }
 
-    score
-}
-
-fn scenic_score_bottom(tree_map: &[i8], idx: usize, width: usize, height: usize) -> usize {
-    let mut i = idx;
-    let mut score = 0;
-
-    while i < width * (height - 1) {
-        score += 1;
-        i += width;
-        if tree_map[i] >= tree_map[idx] {
-            break;
-        }
-    }
-
-    score
-}
-
-fn calculate_scenic_score(tree_map: &[i8], idx: usize, width: usize, height: usize) -> usize {
-    scenic_score_left(tree_map, idx, width)
-        * scenic_score_right(tree_map, idx, width)
-        * scenic_score_top(tree_map, idx, width)
-        * scenic_score_bottom(tree_map, idx, width, height)
-}
-
-fn parse_tree_map(s: &str) -> Vec<Vec<i8>> {
-    s.lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).expect("Couldn't parse tree into size"))
-                .map(|i| i as i8)
-                .collect::<Vec<i8>>()
-        })
-        .collect()
-}
-
-pub fn step1(s: &str) -> Answer {
-    let tree_map = parse_tree_map(s);
-
-    let width = tree_map[0].len();
-    let height = tree_map.len();
-    let mut visible_tree_count = width * 2 + height * 2 - 4;
-
-    let tree_map: Vec<i8> = tree_map.into_iter().flatten().collect();
-
-    for i in 1..(height - 1) {
-        for j in 1..(width - 1) {
-            if is_visible(&tree_map, (i * width) + j, width, height) {
-                visible_tree_count += 1;
-            }
-        }
+    #[test]
+    fn step2_finds_correct_answer() {
+        assert_eq!(step2(INPUT), Answer::Unsigned(8));
     }
-
-    visible_tree_count.into()
-}
-
-pub fn step2(s: &str) -> Answer {
-    let tree_map = parse_tree_map(s);
-    let width = tree_map[0].len();
-    let height = tree_map.len();
-
-    let tree_map: Vec<i8> = tree_map.into_iter().flatten().collect();
-
-    let max_scenic_score = tree_map
-        .iter()
-        .enumerate()
-        .map(|(i, _)| calculate_scenic_score(&tree_map, i, width, height))
-        .max()
-        .expect("Couldn't find a maximum scenic score");
-
-    max_scenic_score.into()
 }
@@ -56,18 +56,9 @@ impl FromStr for Motion {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use Direction::*;
-
         let parts: Vec<&str> = s.split(' ').collect();
         let count = parts[1].parse().unwrap();
-
-        let direction = match parts[0] {
-            "U" => Up,
-            "D" => Down,
-            "L" => Left,
-            "R" => Right,
-            _ => unreachable!(),
-        };
+        let direction = parts[0].parse().unwrap();
 
         Ok(Motion { direction, count })
     }
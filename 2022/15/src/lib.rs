@@ -1,7 +1,10 @@
-use common::{Answer, Coordinates, distances::manhattan};
+use common::{
+    Answer, Coordinates,
+    distances::{manhattan, manhattan_ring},
+};
 use itertools::Itertools;
 use regex::Regex;
-use std::{collections::HashSet, sync::LazyLock};
+use std::sync::LazyLock;
 
 type IntType = i64;
 type Coord = Coordinates<IntType>;
@@ -42,35 +45,8 @@ impl SensorReadout {
         }
     }
 
-    fn perimeter(&self) -> HashSet<Coord> {
-        let distance = self.distance + 1;
-        let sx = self.sensor.x();
-        let sy = self.sensor.y();
-
-        let mut points = Vec::new();
-
-        // Top-right edge
-        for i in 0..=distance {
-            points.push(Coordinates::new(sx + i, sy - distance + i));
-        }
-        // Bottom-right edge
-        for i in 0..=distance {
-            points.push(Coordinates::new(sx + distance - i, sy + i));
-        }
-        // Bottom-left edge
-        for i in 0..=distance {
-            points.push(Coordinates::new(sx - i, sy + distance - i));
-        }
-        // Top-left edge
-        for i in 0..=distance {
-            points.push(Coordinates::new(sx - distance + i, sy - i));
-        }
-
-        // Deduplicate and filter points within bounds
-        points
-            .into_iter()
-            .filter(|p| p.x() >= 0 && p.x() < 4_000_000 && p.y() >= 0 && p.y() < 4_000_000)
-            .collect()
+    fn perimeter(&self) -> impl Iterator<Item = Coord> {
+        manhattan_ring(self.sensor, self.distance + 1)
     }
 }
 
@@ -1,11 +1,10 @@
-use common::Answer;
+use common::{Answer, math::lcm_all};
 use nom::{
     IResult, Parser,
     bytes::complete::tag,
     character::complete::alphanumeric1,
     sequence::{delimited, separated_pair},
 };
-use num::integer::lcm;
 use rustc_hash::FxHashMap;
 
 #[derive(Debug)]
@@ -89,12 +88,12 @@ pub fn step1(s: &str) -> Answer {
 pub fn step2(s: &str) -> Answer {
     let map = Map::from(s);
 
-    let ghost_steps = map
-        .map
-        .keys()
-        .filter(|k| k.ends_with('A'))
-        .map(|k| map.traverse(k, |s| s.ends_with('Z')))
-        .fold(1, lcm);
+    let ghost_steps = lcm_all(
+        map.map
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|k| map.traverse(k, |s| s.ends_with('Z'))),
+    );
 
     ghost_steps.into()
 }
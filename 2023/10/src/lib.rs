@@ -1,4 +1,4 @@
-use common::Answer;
+use common::{Answer, Coordinates, Polygon};
 use std::fmt::{self, Display, Formatter};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -203,18 +203,46 @@ pub fn step1(s: &str) -> Answer {
 
 pub fn step2(s: &str) -> Answer {
     let path = get_path(s);
+    let polygon = path
+        .into_iter()
+        .map(|(x, y)| Coordinates::new(x as i64, y as i64))
+        .collect::<Polygon<_>>();
 
-    let area = path
-        .windows(2)
-        .map(|w| {
-            let (x1, y1) = w[0];
-            let (x2, y2) = w[1];
+    polygon.interior_points().into()
+}
 
-            (x1 * y2) as i32 - (x2 * y1) as i32
-        })
-        .sum::<i32>()
-        .abs()
-        / 2;
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ENCLOSED: &str = r#"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."#;
+
+    const LARGER: &str = r#".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ..."#;
+
+    #[test]
+    fn step2_counts_enclosed_tiles() {
+        assert_eq!(step2(ENCLOSED), Answer::Signed(4));
+    }
 
-    (area + 1 - (path.len() / 2) as i32).into()
+    #[test]
+    fn step2_counts_enclosed_tiles_in_larger_loop() {
+        assert_eq!(step2(LARGER), Answer::Signed(8));
+    }
 }
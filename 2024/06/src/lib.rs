@@ -1,4 +1,4 @@
-use common::{Answer, BooleanGrid, Coordinates, Direction};
+use common::{Answer, BooleanBoundedGrid, Coordinates, Direction};
 use itertools::Itertools;
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
@@ -6,20 +6,8 @@ use rustc_hash::FxHashSet;
 type IntType = i16;
 type Coords = Coordinates<IntType>;
 
-fn parse(s: &str) -> (BooleanGrid<IntType>, Coords) {
-    let mut map = BooleanGrid::new();
-    let mut guard = None;
-
-    s.lines().enumerate().for_each(|(y, l)| {
-        l.chars().enumerate().for_each(|(x, c)| {
-            let pos = (x as IntType, y as IntType).into();
-            match c {
-                '^' => guard = Some(pos),
-                '#' => map.mark(pos),
-                _ => {}
-            }
-        })
-    });
+fn parse(s: &str) -> (BooleanBoundedGrid<IntType>, Coords) {
+    let (map, [guard]) = BooleanBoundedGrid::parse_with_markers(s, ['^'], |c| c == '#');
 
     (map, guard.unwrap())
 }
@@ -29,7 +17,7 @@ enum Outcome {
     OutOfBounds(FxHashSet<(Coords, Direction)>),
 }
 
-fn simulate_route(obstacles: &BooleanGrid<IntType>, start: Coords) -> Outcome {
+fn simulate_route(obstacles: &BooleanBoundedGrid<IntType>, start: Coords) -> Outcome {
     let mut guard_locations = FxHashSet::default();
     let mut direction = Direction::Up;
     let mut guard_pos = start;
@@ -1,70 +1,39 @@
-use common::{Answer, BooleanBoundedGrid, BooleanGrid, Coordinates};
-use rustc_hash::FxHashMap;
+use common::{Answer, BoundedGrid, Coordinates};
+use rustc_hash::{FxHashMap, FxHashSet};
 
 type IntType = i8;
 type Coords = Coordinates<IntType>;
-type FreqMap = FxHashMap<char, BooleanGrid<IntType>>;
+type Map = BoundedGrid<IntType, char>;
 
-fn get_grid_size(s: &str) -> (Coords, Coords) {
-    let max_x = s.lines().next().map(|l| l.chars().count()).unwrap();
-    let max_y = s.lines().count();
+fn parse(s: &str) -> (Map, FxHashMap<char, Vec<Coords>>) {
+    let map = Map::parse(s, |c| (c != '.').then_some(c));
+    let mut frequencies = FxHashMap::<_, Vec<_>>::default();
 
-    ((0, 0).into(), (max_x - 1, max_y - 1).into())
-}
-
-fn parse(s: &str) -> FreqMap {
-    let mut frequencies = FreqMap::default();
-
-    s.lines().enumerate().for_each(|(y, l)| {
-        l.chars()
-            .enumerate()
-            .filter(|&(_, c)| c != '.')
-            .for_each(|(x, c)| {
-                frequencies
-                    .entry(c)
-                    .or_default()
-                    .mark(Coords::new(x as IntType, y as IntType))
-            });
-    });
+    map.iter()
+        .for_each(|(&pos, &c)| frequencies.entry(c).or_default().push(pos));
 
-    frequencies
-}
-
-fn store_antinodes(
-    antinodes: &mut BooleanBoundedGrid<IntType>,
-    mut a: Coords,
-    b: Coords,
-    resonance: bool,
-) {
-    let diff = a - b;
-    loop {
-        a = a + diff;
-        let inserted = antinodes.mark(a);
-
-        if !resonance || !inserted {
-            break;
-        }
-    }
+    (map, frequencies)
 }
 
 fn calculate_antinodes(s: &str, resonance: bool) -> usize {
-    let (min, max) = get_grid_size(s);
-    let mut antinodes = BooleanBoundedGrid::new(min, max);
-    let frequencies = parse(s);
-
-    frequencies.iter().for_each(|(_, antennas)| {
-        antennas.iter().for_each(|&pos| {
-            if resonance {
-                antinodes.mark(pos);
-            }
+    let (map, frequencies) = &parse(s);
+    let reach = if resonance { usize::MAX } else { 1 };
 
-            antennas.iter().filter(|&&p| p != pos).for_each(|&other| {
-                store_antinodes(&mut antinodes, pos, other, resonance);
+    frequencies
+        .values()
+        .flat_map(|antennas| {
+            antennas.iter().flat_map(|&pos| {
+                let own = resonance.then_some(pos);
+                let lines = antennas
+                    .iter()
+                    .filter(move |&&other| other != pos)
+                    .flat_map(move |&other| map.ray(pos, pos - other).take(reach));
+
+                own.into_iter().chain(lines)
             })
         })
-    });
-
-    antinodes.len()
+        .collect::<FxHashSet<_>>()
+        .len()
 }
 
 pub fn step1(s: &str) -> Answer {
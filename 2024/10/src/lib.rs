@@ -1,4 +1,4 @@
-use common::{Answer, Coordinates, Grid};
+use common::{Answer, Coordinates, FourWay, Grid};
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 
@@ -9,19 +9,10 @@ type Path = Vec<Coords>;
 type CellRef<'a> = (Coords, &'a Height);
 type EndValidator = fn(Coords, &Height) -> bool;
 type NeighborValidator = fn(CellRef, CellRef) -> bool;
-type BFSFunc = fn(&Map, Coords, EndValidator, NeighborValidator) -> Vec<Path>;
+type BFSFunc = fn(&Map, &FourWay, Coords, EndValidator, NeighborValidator) -> Vec<Path>;
 
 fn parse(s: &str) -> Map {
-    let mut map = Map::new();
-
-    s.lines().enumerate().for_each(|(y, l)| {
-        l.chars().enumerate().for_each(|(x, c)| {
-            let val = c.to_digit(10).unwrap();
-            map.store((x, y).into(), val as u8);
-        })
-    });
-
-    map
+    Map::parse(s, |c| c.to_digit(10).map(|val| val as u8))
 }
 
 #[inline]
@@ -44,7 +35,7 @@ fn count_trails(map: &Map, f: BFSFunc) -> usize {
         .par_bridge()
         .filter(is_trailhead)
         .map(|(pos, _)| *pos)
-        .flat_map(|start| f(map, start, is_trailend, is_trail))
+        .flat_map(|start| f(map, &FourWay, start, is_trailend, is_trail))
         .fold(FxHashMap::<Coords, usize>::default, |mut acc, trail| {
             let head = *trail.first().unwrap();
             *acc.entry(head).or_default() += 1;
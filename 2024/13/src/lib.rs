@@ -1,5 +1,4 @@
-use common::{Answer, Coordinates};
-use num::Integer;
+use common::{Answer, Coordinates, Matrix};
 
 type IntType = i64;
 type Coords = Coordinates<IntType>;
@@ -13,17 +12,19 @@ struct ClawMachine {
 
 impl ClawMachine {
     fn solve(&self) -> Option<(IntType, IntType)> {
-        let det = self.a.x() * self.b.y() - self.a.y() * self.b.x();
-        let det_a = self.prize.x() * self.b.y() - self.prize.y() * self.b.x();
-        let det_b = self.prize.y() * self.a.x() - self.prize.x() * self.a.y();
+        let buttons = Matrix::from_rows(vec![
+            vec![self.a.x(), self.b.x()],
+            vec![self.a.y(), self.b.y()],
+        ]);
+        let solution = buttons.solve_exact(&[self.prize.x(), self.prize.y()])?;
 
-        let (a_presses, a_rem) = det_a.div_rem(&det);
-        let (b_presses, b_rem) = det_b.div_rem(&det);
+        if !solution.is_unique() {
+            return None;
+        }
 
-        if a_rem == 0 && b_rem == 0 {
-            Some((a_presses, b_presses))
-        } else {
-            None
+        match solution.particular() {
+            [a, b] => Some((a.to_integer()?, b.to_integer()?)),
+            _ => None,
         }
     }
 
@@ -81,3 +82,34 @@ pub fn step2(s: &str) -> Answer {
         .sum::<IntType>()
         .into()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = r#"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279"#;
+
+    #[test]
+    fn step1_finds_correct_answer() {
+        assert_eq!(step1(INPUT), Answer::Signed(480));
+    }
+
+    #[test]
+    fn step2_finds_correct_answer() {
+        assert_eq!(step2(INPUT), Answer::Signed(875318608908));
+    }
+}
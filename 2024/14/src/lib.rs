@@ -1,5 +1,4 @@
-use common::{Answer, Coordinates};
-use num::Integer;
+use common::{Answer, Coordinates, Rectangle};
 use rustc_hash::FxHashSet;
 
 type IntType = i32;
@@ -13,18 +12,7 @@ struct Robot {
 
 impl Robot {
     fn simulate(&mut self, n: IntType, field: Coords) {
-        let mut x = (self.pos.x() + self.movement.x() * n) % field.x();
-        let mut y = (self.pos.y() + self.movement.y() * n) % field.y();
-
-        if x < 0 {
-            x += field.x();
-        }
-
-        if y < 0 {
-            y += field.y();
-        }
-
-        self.pos = (x, y).into();
+        self.pos = self.pos.wrapping_add(self.movement * n, field);
     }
 }
 
@@ -59,28 +47,6 @@ fn parse(s: &str) -> impl Iterator<Item = Robot> + '_ {
     })
 }
 
-fn quadrants(field: Coords) -> [(Coords, Coords); 4] {
-    let (half_x, diff_x) = field.x().div_rem(&2);
-    let (half_y, diff_y) = field.y().div_rem(&2);
-
-    [
-        ((0, 0).into(), (half_x, half_y).into()),
-        ((half_x + diff_x, 0).into(), (field.x(), half_y).into()),
-        ((0, half_y + diff_y).into(), (half_x, field.y()).into()),
-        (
-            (half_x + diff_x, half_y + diff_y).into(),
-            (field.x(), field.y()).into(),
-        ),
-    ]
-}
-
-fn filter_quadrant(quadrant: (Coords, Coords), pos: Coords) -> bool {
-    pos.x() >= quadrant.0.x()
-        && pos.x() < quadrant.1.x()
-        && pos.y() >= quadrant.0.y()
-        && pos.y() < quadrant.1.y()
-}
-
 fn calculate_safety_factor(
     robots: impl Iterator<Item = Robot>,
     seconds: IntType,
@@ -92,13 +58,14 @@ fn calculate_safety_factor(
             r
         })
         .collect::<Vec<_>>();
-    let quadrants = quadrants(field);
+    let field = Rectangle::with_size(Coords::new(0, 0), field.x(), field.y()).unwrap();
 
-    (0..4)
-        .map(|i| {
+    field
+        .quadrants()
+        .map(|quadrant| {
             robots
                 .iter()
-                .filter(|r| filter_quadrant(quadrants[i], r.pos))
+                .filter(|r| quadrant.contains(&r.pos))
                 .count()
                 .max(1)
         })
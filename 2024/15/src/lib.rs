@@ -32,28 +32,13 @@ type WarehouseMap = Grid<IntType, Cell>;
 type Moves = VecDeque<Direction>;
 
 fn parse_warehouse(s: &str) -> Warehouse {
-    let mut warehouse_map = WarehouseMap::new();
-
-    s.lines().enumerate().for_each(|(y, line)| {
-        line.chars()
-            .enumerate()
-            .filter(|&(_, c)| c != '.')
-            .for_each(|(x, c)| warehouse_map.store((x, y).into(), c.into()))
-    });
-
-    Warehouse::new(warehouse_map)
+    Warehouse::new(WarehouseMap::parse(s, |c| (c != '.').then(|| c.into())))
 }
 
 fn parse_moves(s: &str) -> Moves {
     s.chars()
         .filter(|&c| c != '\n')
-        .map(|c| match c {
-            '^' => Direction::Up,
-            '>' => Direction::Right,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            _ => unreachable!(),
-        })
+        .map(|c| Direction::try_from(c).expect("Invalid move"))
         .collect()
 }
 
@@ -180,7 +165,7 @@ impl Warehouse {
                 while end < destination {
                     self.map.store(end, Cell::BoxLeft);
                     self.map.store(end.right(), Cell::BoxRight);
-                    end = end + (2, 0).into();
+                    end += (2, 0).into();
                 }
                 self.map.remove(&destination);
                 self.robot = destination;
@@ -190,7 +175,7 @@ impl Warehouse {
                 while destination < end {
                     self.map.store(end, Cell::BoxRight);
                     self.map.store(end.left(), Cell::BoxLeft);
-                    end = end + (-2, 0).into();
+                    end += (-2, 0).into();
                 }
                 self.map.remove(&destination);
                 self.robot = destination;
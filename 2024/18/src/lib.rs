@@ -1,17 +1,41 @@
-use common::{Answer, BooleanGrid, Coordinates};
+use common::{Answer, Coordinates, FourWay, Grid};
 
 type IntType = i8;
-type Memory = BooleanGrid<IntType>;
+type Memory = Grid<IntType, bool>;
 type Coords = Coordinates<IntType>;
 
 fn parse(s: &str) -> impl Iterator<Item = Coords> + '_ {
     s.lines().map(|s| Coordinates::try_from(s).unwrap())
 }
 
-fn find_shortest_path(s: &str, obstacles: usize, max: Coords) -> usize {
+/// Memory spanning the whole area from `(0, 0)` to `max`, with every cell
+/// still free.
+fn memory(max: Coords) -> Memory {
     let mut mem = Memory::new();
-    parse(s).take(obstacles).for_each(|pos| mem.mark(pos));
-    mem.bfs((0, 0).into(), max, false).len() - 1
+    for y in 0..=max.y() {
+        for x in 0..=max.x() {
+            mem.store((x, y).into(), false);
+        }
+    }
+
+    mem
+}
+
+fn shortest_path(mem: &Memory, max: Coords) -> Vec<Coords> {
+    mem.bfs(
+        &FourWay,
+        (0, 0).into(),
+        |pos, _| pos == max,
+        |_, (_, &corrupted)| !corrupted,
+    )
+}
+
+fn find_shortest_path(s: &str, obstacles: usize, max: Coords) -> usize {
+    let mut mem = memory(max);
+    parse(s)
+        .take(obstacles)
+        .for_each(|pos| mem.store(pos, true));
+    shortest_path(&mem, max).len() - 1
 }
 
 pub fn step1(s: &str) -> Answer {
@@ -19,15 +43,15 @@ pub fn step1(s: &str) -> Answer {
 }
 
 fn find_first_blocker(s: &str, obstacles: usize, max: Coords) -> Coords {
-    let mut mem = Memory::new();
+    let mut mem = memory(max);
     let mut coords = parse(s);
-    (0..obstacles).for_each(|_| mem.mark(coords.next().unwrap()));
+    (0..obstacles).for_each(|_| mem.store(coords.next().unwrap(), true));
 
     loop {
         let pos = coords.next().unwrap();
-        mem.mark(pos);
+        mem.store(pos, true);
 
-        if mem.bfs((0, 0).into(), max, false).is_empty() {
+        if shortest_path(&mem, max).is_empty() {
             return pos;
         }
     }
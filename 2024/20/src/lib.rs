@@ -1,4 +1,6 @@
-use common::{Answer, BooleanGrid, Coordinates, distances::manhattan};
+use common::{
+    Answer, BooleanGrid, Coordinates, FourWay, Neighborhood, Offsets, distances::manhattan,
+};
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 
@@ -6,72 +8,69 @@ type IntType = i16;
 type Coords = Coordinates<IntType>;
 
 fn parse(s: &str) -> (FxHashMap<Coords, usize>, Coords) {
-    let mut map = BooleanGrid::new();
-    let mut start = (0, 0).into();
-    let mut end = (0, 0).into();
+    let (map, [start, end]) =
+        BooleanGrid::parse_with_markers(s, ['S', 'E'], |c| matches!(c, '.' | 'S' | 'E'));
 
-    s.lines().enumerate().for_each(|(y, line)| {
-        line.chars().enumerate().for_each(|(x, c)| match c {
-            '.' => map.mark((x, y).into()),
-            'S' => {
-                map.mark((x, y).into());
-                start = (x, y).into();
-            }
-            'E' => {
-                map.mark((x, y).into());
-                end = (x, y).into();
-            }
-            _ => {}
-        })
-    });
-
-    let path = map
-        .bfs(start, end, true)
-        .into_iter()
-        .enumerate()
-        .map(|(a, b)| (b, a))
-        .collect::<FxHashMap<_, _>>();
-
-    (path, end)
-}
-
-fn extended_neighbors(pos: Coords) -> Vec<Coords> {
-    vec![
-        pos.up().up(),
-        pos.right().right(),
-        pos.down().down(),
-        pos.left().left(),
-    ]
+    (
+        map.distances_from(&FourWay, [start.unwrap()], true),
+        end.unwrap(),
+    )
 }
 
-pub fn step1(s: &str) -> Answer {
+fn count_cheats(s: &str, max_length: IntType, min_saving: usize) -> usize {
     let (path, end) = parse(s);
+    let cheats = Offsets::manhattan(max_length);
 
     path.par_iter()
         .filter(|(pos, _)| **pos != end)
-        .map(|(pos, dist)| {
-            extended_neighbors(*pos)
-                .into_iter()
-                .filter(|pos| path.get(pos).unwrap_or(&0).saturating_sub(dist + 2) >= 100)
+        .map(|(&pos, dist)| {
+            cheats
+                .neighbors(pos)
+                .filter(|p| {
+                    path.get(p).is_some_and(|d| {
+                        d.saturating_sub(dist + manhattan(pos, *p) as usize) >= min_saving
+                    })
+                })
                 .count()
         })
-        .sum::<usize>()
-        .into()
+        .sum()
+}
+
+pub fn step1(s: &str) -> Answer {
+    count_cheats(s, 2, 100).into()
 }
 
 pub fn step2(s: &str) -> Answer {
-    let (path, end) = parse(s);
+    count_cheats(s, 20, 100).into()
+}
 
-    path.par_iter()
-        .filter(|(pos, _)| **pos != end)
-        .map(|(pos, dist)| {
-            path.iter()
-                .filter(|&(p, d)| {
-                    let distance = manhattan(*pos, *p) as usize;
-                    pos != p && distance <= 20 && d.saturating_sub(dist + distance) >= 100
-                })
-                .count()
-        })
-        .sum::<usize>()
-        .into()
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = r#"###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############"#;
+
+    #[test]
+    fn count_cheats_finds_short_cheats() {
+        assert_eq!(count_cheats(INPUT, 2, 1), 44);
+    }
+
+    #[test]
+    fn count_cheats_finds_long_cheats() {
+        assert_eq!(count_cheats(INPUT, 20, 50), 285);
+    }
 }
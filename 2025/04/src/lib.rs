@@ -1,58 +1,30 @@
-use common::{Answer, BooleanGrid, Coordinates};
+use common::{Answer, Automaton, BooleanGrid};
 
 type IntType = i16;
 type RollGrid = BooleanGrid<IntType>;
-type Coords = Coordinates<IntType>;
 
-fn parse(s: &str) -> RollGrid {
-    let mut grid = RollGrid::new();
+fn forklifts(s: &str) -> Automaton<IntType, impl Fn(bool, usize) -> bool> {
+    let grid = RollGrid::parse(s, |c| c == '@');
 
-    s.lines().enumerate().for_each(|(y, l)| {
-        l.chars().enumerate().for_each(|(x, c)| {
-            if c == '@' {
-                grid.mark(Coordinates::new(x as IntType, y as IntType))
-            }
-        })
-    });
-
-    grid
-}
-
-fn removeable_rolls(grid: &RollGrid) -> Vec<Coords> {
-    grid.iter()
-        .filter(|pos| {
-            pos.neighbors_8()
-                .iter()
-                .filter(|pos| grid.contains(pos))
-                .count()
-                < 4
-        })
-        .copied()
-        .collect()
+    Automaton::new(grid.iter().copied(), |roll, neighbors| {
+        roll && neighbors >= 4
+    })
 }
 
 pub fn step1(s: &str) -> Answer {
-    let grid = parse(s);
+    let mut forklifts = forklifts(s);
+    let rolls = forklifts.len();
+    forklifts.step();
 
-    removeable_rolls(&grid).len().into()
+    (rolls - forklifts.len()).into()
 }
 
 pub fn step2(s: &str) -> Answer {
-    let mut grid = parse(s);
-    let mut removeable = 0;
-
-    loop {
-        let rolls = removeable_rolls(&grid);
-
-        if rolls.is_empty() {
-            break;
-        }
+    let mut forklifts = forklifts(s);
+    let rolls = forklifts.len();
+    forklifts.run_until_stable();
 
-        removeable += rolls.len();
-        rolls.iter().for_each(|pos| grid.remove(pos));
-    }
-
-    removeable.into()
+    (rolls - forklifts.len()).into()
 }
 
 #[cfg(test)]
@@ -74,4 +46,9 @@ mod test {
     fn step1_finds_corret_sample_answer() {
         assert_eq!(step1(INPUT), Answer::Unsigned(13));
     }
+
+    #[test]
+    fn step2_finds_correct_sample_answer() {
+        assert_eq!(step2(INPUT), Answer::Unsigned(43));
+    }
 }
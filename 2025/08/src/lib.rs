@@ -1,42 +1,20 @@
-use common::Answer;
+use common::{Answer, Coordinates3, distances::squared_euclidian_3d};
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
-type IntType = u32;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
-struct Xyz {
-    x: IntType,
-    y: IntType,
-    z: IntType,
-}
-
-fn distance(a: &Xyz, b: &Xyz) -> u64 {
-    let dx = a.x as i64 - b.x as i64;
-    let dy = a.y as i64 - b.y as i64;
-    let dz = a.z as i64 - b.z as i64;
-
-    (dx * dx + dy * dy + dz * dz) as u64
-}
+type IntType = u64;
+type Xyz = Coordinates3<IntType>;
 
 fn parse(s: &str) -> Vec<Xyz> {
     s.lines()
-        .map(|l| {
-            let mut points = l.split(',').map(|s| s.parse::<IntType>().unwrap());
-
-            Xyz {
-                x: points.next().expect("Expected data at beginning of line"),
-                y: points.next().expect("Expected second element on line"),
-                z: points.next().expect("Expected third element on line"),
-            }
-        })
+        .map(|l| Xyz::try_from(l).expect("Expected three integers on line"))
         .collect()
 }
 
 fn measure_distances(boxes: &[Xyz]) -> Vec<(u64, usize, usize)> {
     (0..boxes.len())
         .flat_map(move |i| {
-            ((i + 1)..boxes.len()).map(move |j| (distance(&boxes[i], &boxes[j]), i, j))
+            ((i + 1)..boxes.len()).map(move |j| (squared_euclidian_3d(boxes[i], boxes[j]), i, j))
         })
         .sorted_by_key(|d| d.0)
         .collect()
@@ -122,7 +100,7 @@ pub fn step2(s: &str) -> Answer {
         }
     }
 
-    (a.x * b.x).into()
+    (a.x() * b.x()).into()
 }
 
 #[cfg(test)]
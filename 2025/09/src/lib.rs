@@ -1,4 +1,4 @@
-use common::{Answer, Coordinates};
+use common::{Answer, Compression, Coordinates, Polygon, PrefixSums, Rectangle};
 use itertools::Itertools;
 
 type IntType = usize;
@@ -16,10 +16,7 @@ fn parse(s: &str) -> impl Iterator<Item = Coords> {
 }
 
 fn rectangle_area(a: Coords, b: Coords) -> IntType {
-    let x = a.x().abs_diff(b.x()) + 1;
-    let y = a.y().abs_diff(b.y()) + 1;
-
-    x * y
+    Rectangle::new(a, b).area()
 }
 
 pub fn step1(s: &str) -> Answer {
@@ -31,46 +28,67 @@ pub fn step1(s: &str) -> Answer {
         .into()
 }
 
-fn edges(reds: &[Coords]) -> Vec<(Coords, Coords)> {
-    let mut edges = reds.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>();
-    edges.push((reds[0], reds[reds.len() - 1]));
-
-    edges
-}
-
-fn crosses_edges(rectangle: (Coords, Coords), edges: &[(Coords, Coords)]) -> bool {
-    let min_x = rectangle.0.x().min(rectangle.1.x());
-    let max_x = rectangle.0.x().max(rectangle.1.x());
-    let min_y = rectangle.0.y().min(rectangle.1.y());
-    let max_y = rectangle.0.y().max(rectangle.1.y());
+/// Compresses the red tiles and marks every compressed cell that is on or
+/// inside the loop, returning prefix sums counting those cells.
+fn green_tiles(reds: &[Coords]) -> (Compression<IntType>, PrefixSums<usize>) {
+    let compression = Compression::new(reds.iter().copied());
+    let mut boundary = compression.grid(false);
+
+    Polygon::new(reds.to_vec()).edges().for_each(|edge| {
+        let (a, b) = (
+            compression.compress(edge.start()).unwrap(),
+            compression.compress(edge.end()).unwrap(),
+        );
+
+        a.range_inclusive(b).for_each(|pos| {
+            boundary.store(pos, true);
+        });
+    });
+
+    let mut inside = compression.grid(true);
+    let mut queue = boundary
+        .iter()
+        .filter(|(pos, on_loop)| {
+            !**on_loop
+                && (pos.x() == 0
+                    || pos.y() == 0
+                    || pos.x() == compression.width() - 1
+                    || pos.y() == compression.height() - 1)
+        })
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
+
+    while let Some(pos) = queue.pop() {
+        if inside.get(&pos) == Some(&true) {
+            inside.store(pos, false);
+            queue.extend(
+                boundary
+                    .neighbors(pos)
+                    .filter(|n| boundary.get(n) == Some(&false)),
+            );
+        }
+    }
 
-    edges.iter().any(|edge| {
-        let edge_min_x = edge.0.x().min(edge.1.x());
-        let edge_max_x = edge.0.x().max(edge.1.x());
-        let edge_min_y = edge.0.y().min(edge.1.y());
-        let edge_max_y = edge.0.y().max(edge.1.y());
+    let sums = inside.prefix_sums(|_, &green| usize::from(green));
 
-        min_x < edge_max_x && max_x > edge_min_x && min_y < edge_max_y && max_y > edge_min_y
-    })
+    (compression, sums)
 }
 
 pub fn step2(s: &str) -> Answer {
     let reds = parse(s).collect::<Vec<_>>();
-    let edges = edges(&reds);
+    let (compression, green) = green_tiles(&reds);
 
-    reds.iter()
+    reds.into_iter()
         .combinations(2)
-        .map(|corners| {
-            (
-                (corners[0], corners[1]),
-                rectangle_area(*corners[0], *corners[1]),
-            )
+        .filter(|corners| {
+            let a = compression.compress(corners[0]).unwrap();
+            let b = compression.compress(corners[1]).unwrap();
+
+            green.sum(a, b) == rectangle_area(a, b)
         })
-        .sorted_unstable_by_key(|t| t.1)
-        .rev()
-        .find(|(r, _)| !crosses_edges((*r.0, *r.1), &edges))
-        .map(|(_, area)| area)
-        .unwrap()
+        .map(|corners| rectangle_area(corners[0], corners[1]))
+        .max()
+        .expect("no maximum value found")
         .into()
 }
 
@@ -1,5 +1,4 @@
-use common::Answer;
-use itertools::Itertools;
+use common::{Answer, BitMatrix, Matrix, Rational, math::lcm_all};
 
 type IntType = u16;
 
@@ -42,19 +41,35 @@ fn parse(s: &str) -> Vec<(IntType, Vec<IntType>)> {
     s.lines().map(parse_line).collect()
 }
 
+/// Fewest presses turning on exactly the `target` lights, pressing a button
+/// twice being the same as not pressing it.
+fn fewest_presses(target: IntType, buttons: &[IntType]) -> usize {
+    let lights = IntType::BITS as usize;
+    let mut matrix = BitMatrix::new(lights, buttons.len());
+
+    for (column, button) in buttons.iter().enumerate() {
+        (0..lights).for_each(|row| matrix.set(row, column, button & (1 << row) != 0));
+    }
+
+    let target = (0..lights)
+        .map(|row| target & (1 << row) != 0)
+        .collect::<Vec<_>>();
+
+    matrix
+        .solve(&target)
+        .unwrap()
+        .min_weight()
+        .into_iter()
+        .filter(|&pressed| pressed)
+        .count()
+}
+
 pub fn step1(s: &str) -> Answer {
     let machines = parse(s);
 
     machines
         .iter()
-        .map(|(target, buttons)| {
-            buttons
-                .iter()
-                .powerset()
-                .find(|set| set.iter().fold(0, |a, b| a ^ **b) == *target)
-                .map(|set| set.len())
-                .unwrap()
-        })
+        .map(|(target, buttons)| fewest_presses(*target, buttons))
         .sum::<usize>()
         .into()
 }
@@ -92,10 +107,175 @@ fn parse_part2(s: &str) -> Vec<(Vec<Vec<usize>>, Vec<u16>)> {
     s.lines().map(parse_line_part2).collect()
 }
 
+/// The integer range of `t` such that `t * b >= c`, possibly empty.
+fn at_least(b: i64, c: i64) -> (i64, i64) {
+    match b.signum() {
+        1 => (-((-c).div_euclid(b)), i64::MAX),
+        -1 => (i64::MIN, (-c).div_euclid(-b)),
+        _ if c <= 0 => (i64::MIN, i64::MAX),
+        _ => (1, 0),
+    }
+}
+
+/// The solutions of a machine's `buttons * presses = joltages` system,
+/// scaled by a common denominator so the search only deals with integers.
+struct Presses {
+    particular: Vec<i64>,
+    nullspace: Vec<Vec<i64>>,
+    /// Most presses each button can take, scaled.
+    limits: Vec<i64>,
+    /// For each free variable, the lowest and highest amount the ones after
+    /// it can still add to each button, scaled.
+    slack: Vec<Vec<(i64, i64)>>,
+    /// For each free variable, the lowest amount the ones after it can still
+    /// add to the total, scaled.
+    floor: Vec<i64>,
+    denominator: i64,
+}
+
+impl Presses {
+    fn new(buttons: &[Vec<usize>], joltages: &[u16]) -> Self {
+        // Identical buttons are interchangeable and buttons raising nothing
+        // are useless, so the search only keeps one of each distinct button.
+        let mut buttons = buttons
+            .iter()
+            .filter(|button| !button.is_empty())
+            .map(|button| {
+                let mut button = button.clone();
+                button.sort_unstable();
+                button.dedup();
+                button
+            })
+            .collect::<Vec<_>>();
+        buttons.sort_unstable();
+        buttons.dedup();
+
+        let target = joltages.iter().map(|&j| j as i64).collect::<Vec<_>>();
+        let mut matrix = Matrix::new(target.len(), buttons.len(), 0);
+
+        for (column, button) in buttons.iter().enumerate() {
+            button.iter().for_each(|&row| matrix[(row, column)] = 1);
+        }
+
+        let solution = matrix.solve_exact(&target).unwrap();
+        let denominator = lcm_all(
+            solution
+                .particular()
+                .iter()
+                .chain(solution.nullspace().iter().flatten())
+                .map(Rational::denominator),
+        );
+
+        let scale = |v: &Rational<i64>| v.numerator() * (denominator / v.denominator());
+        let particular = solution.particular().iter().map(scale).collect();
+        let nullspace = solution
+            .nullspace()
+            .iter()
+            .map(|basis| basis.iter().map(scale).collect())
+            .collect::<Vec<Vec<_>>>();
+
+        // A button can't be pressed more often than the lowest counter it raises.
+        let limits = buttons
+            .iter()
+            .map(|button| button.iter().map(|&i| target[i]).min().unwrap_or(0) * denominator)
+            .collect::<Vec<_>>();
+
+        let slack = (0..nullspace.len())
+            .map(|depth| {
+                (0..buttons.len())
+                    .map(|j| {
+                        solution.free_variables()[depth + 1..]
+                            .iter()
+                            .zip(&nullspace[depth + 1..])
+                            .fold((0, 0), |(low, high), (&f, basis)| {
+                                let reach = basis[j] * (limits[f] / denominator);
+                                (low + reach.min(0), high + reach.max(0))
+                            })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let floor = (0..nullspace.len())
+            .map(|depth| {
+                solution.free_variables()[depth + 1..]
+                    .iter()
+                    .zip(&nullspace[depth + 1..])
+                    .map(|(&f, basis)| basis.iter().sum::<i64>().min(0) * (limits[f] / denominator))
+                    .sum()
+            })
+            .collect();
+
+        Self {
+            particular,
+            nullspace,
+            limits,
+            slack,
+            floor,
+            denominator,
+        }
+    }
+
+    /// Fewest total presses, with every button pressed a whole, non-negative
+    /// number of times.
+    fn fewest(&self) -> i64 {
+        let mut best = i64::MAX;
+        self.search(&mut self.particular.clone(), 0, &mut best);
+        assert!(best < i64::MAX, "unreachable joltages");
+
+        best / self.denominator
+    }
+
+    /// Lowers `best` to the smallest scaled total reachable from `presses` by
+    /// picking values for the free variables from `depth` onwards. Each value
+    /// is restricted to the range where every button could still end up
+    /// within its limits.
+    fn search(&self, presses: &mut [i64], depth: usize, best: &mut i64) {
+        let total = presses.iter().sum::<i64>();
+
+        let Some(basis) = self.nullspace.get(depth) else {
+            if presses.iter().all(|&p| p >= 0 && p % self.denominator == 0) {
+                *best = total.min(*best);
+            }
+            return;
+        };
+
+        let (low, high) = (0..presses.len()).fold((0, i64::MAX), |(low, high), j| {
+            let (slack_low, slack_high) = self.slack[depth][j];
+            let (l1, h1) = at_least(basis[j], -presses[j] - slack_high);
+            let (l2, h2) = at_least(-basis[j], presses[j] + slack_low - self.limits[j]);
+
+            (low.max(l1).max(l2), high.min(h1).min(h2))
+        });
+
+        if low > high {
+            return;
+        }
+
+        // Values are tried from the cheaper end, so once the total can't be
+        // improved on anymore, neither can it with the values after.
+        let cost = basis.iter().sum::<i64>();
+        for i in 0..=high - low {
+            let t = if cost >= 0 { low + i } else { high - i };
+            if total + t * cost + self.floor[depth] >= *best {
+                break;
+            }
+
+            presses.iter_mut().zip(basis).for_each(|(p, b)| *p += t * b);
+            self.search(presses, depth + 1, best);
+            presses.iter_mut().zip(basis).for_each(|(p, b)| *p -= t * b);
+        }
+    }
+}
+
 pub fn step2(s: &str) -> Answer {
-    let _machines = parse_part2(s);
+    let machines = parse_part2(s);
 
-    ().into()
+    machines
+        .iter()
+        .map(|(buttons, joltages)| Presses::new(buttons, joltages).fewest() as u64)
+        .sum::<u64>()
+        .into()
 }
 
 #[cfg(test)]
@@ -131,10 +311,15 @@ mod test {
         assert_eq!(step1(INPUT), Answer::Unsigned(7));
     }
 
-    /*
+    #[test]
+    fn step2_merges_identical_buttons() {
+        let input = "[.##.] (3) (1,3) (3) (2) (2,3) (1,3) (0,2) (0,1) (0,2) {3,5,4,7}";
+
+        assert_eq!(step2(input), Answer::Unsigned(10));
+    }
+
     #[test]
     fn step2_computes_expected_sample_result() {
         assert_eq!(step2(INPUT), Answer::Unsigned(33));
     }
-    */
 }
@@ -0,0 +1,56 @@
+use common::sieve::divisor_sums;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+const LIMIT: usize = 100_000;
+
+/// Same trial division as `utils::factors`, which can't be measured directly
+/// because it is memoized across iterations.
+fn factors(n: u64) -> Vec<u64> {
+    let mut factors = vec![1, n];
+
+    let mut i = 2;
+    while i * i <= n {
+        if n.is_multiple_of(i) {
+            factors.push(i);
+
+            if i * i != n {
+                factors.push(n / i);
+            }
+        }
+
+        i += 1;
+    }
+
+    factors.sort();
+    factors.dedup();
+
+    factors
+}
+
+/// The brute force 2015/20 used before the sieve.
+fn houses(max: usize) -> Vec<u32> {
+    let mut houses = vec![0; max];
+
+    for i in 1..max {
+        for j in (i..max).step_by(i) {
+            houses[j] += i as u32;
+        }
+    }
+
+    houses
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("divisor_sums_sieve", |b| b.iter(|| divisor_sums(LIMIT)));
+    c.bench_function("divisor_sums_factors", |b| {
+        b.iter(|| {
+            (0..=LIMIT as u64)
+                .map(|n| if n == 0 { 0 } else { factors(n).iter().sum() })
+                .collect::<Vec<u64>>()
+        })
+    });
+    c.bench_function("divisor_sums_houses", |b| b.iter(|| houses(LIMIT + 1)));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
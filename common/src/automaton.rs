@@ -0,0 +1,386 @@
+use crate::{
+    BooleanGrid, Coordinates,
+    neighborhood::{EightWay, Neighborhood},
+};
+use num_traits::PrimInt;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{hash::Hash, mem};
+
+/// Decides whether a cell is alive in the next generation, given whether it
+/// is alive now and how many of its neighbours are.
+///
+/// Implemented by [`BirthSurvival`] and any closure `Fn(bool, usize) -> bool`.
+pub trait Rule {
+    fn next(&self, alive: bool, neighbors: usize) -> bool;
+}
+
+/// A rule in the usual `B3/S23` notation: dead cells come alive with one of
+/// the `birth` counts, living cells stay alive with one of the `survival`
+/// counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BirthSurvival {
+    birth: u32,
+    survival: u32,
+}
+
+impl BirthSurvival {
+    /// Counts of 32 or more are ignored, such rules never applying.
+    #[must_use]
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let mask = |counts: &[usize]| {
+            counts
+                .iter()
+                .filter(|&&n| n < 32)
+                .fold(0, |mask, &n| mask | (1 << n))
+        };
+
+        Self {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Conway's Game of Life, `B3/S23`.
+    #[must_use]
+    pub fn conway() -> Self {
+        Self::new(&[3], &[2, 3])
+    }
+}
+
+impl Rule for BirthSurvival {
+    #[inline]
+    fn next(&self, alive: bool, neighbors: usize) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+
+        neighbors < 32 && mask & (1 << neighbors) != 0
+    }
+}
+
+impl<F: Fn(bool, usize) -> bool> Rule for F {
+    #[inline]
+    fn next(&self, alive: bool, neighbors: usize) -> bool {
+        self(alive, neighbors)
+    }
+}
+
+/// Runs a cellular automaton over a set of living cells.
+///
+/// Only the cells that changed in the last generation and their neighbours
+/// are re-evaluated, which assumes the neighbourhood is symmetric.
+#[derive(Debug, Clone)]
+pub struct Automaton<T, R, N = EightWay> {
+    rule: R,
+    neighborhood: N,
+    alive: FxHashSet<Coordinates<T>>,
+    next: FxHashSet<Coordinates<T>>,
+    changed: Option<Vec<Coordinates<T>>>,
+    pinned: FxHashSet<Coordinates<T>>,
+    bounds: Option<(Coordinates<T>, Coordinates<T>)>,
+    generation: usize,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Eq + Hash + Default + PrimInt,
+    R: Rule,
+{
+    /// Starts an automaton with the given living cells, using the eight
+    /// surrounding cells as neighbours.
+    #[must_use]
+    pub fn new<I: IntoIterator<Item = Coordinates<T>>>(cells: I, rule: R) -> Self {
+        Self {
+            rule,
+            neighborhood: EightWay,
+            alive: cells.into_iter().collect(),
+            next: FxHashSet::default(),
+            changed: None,
+            pinned: FxHashSet::default(),
+            bounds: None,
+            generation: 0,
+        }
+    }
+}
+
+impl<T, R, N> Automaton<T, R, N>
+where
+    T: Eq + Hash + Default + PrimInt,
+    R: Rule,
+    N: Neighborhood<T>,
+{
+    /// Counts neighbours through `neighborhood` instead.
+    #[must_use]
+    pub fn neighborhood<M: Neighborhood<T>>(self, neighborhood: M) -> Automaton<T, R, M> {
+        Automaton {
+            rule: self.rule,
+            neighborhood,
+            alive: self.alive,
+            next: self.next,
+            changed: None,
+            pinned: self.pinned,
+            bounds: self.bounds,
+            generation: self.generation,
+        }
+    }
+
+    /// Keeps every cell outside of `min..=max` dead.
+    #[must_use]
+    pub fn bounded(mut self, min: Coordinates<T>, max: Coordinates<T>) -> Self {
+        self.bounds = Some((min, max));
+        self.alive.retain(|pos| within(Some((min, max)), pos));
+        self.changed = None;
+
+        self
+    }
+
+    /// Keeps the given cells alive in every generation.
+    #[must_use]
+    pub fn pinned<I: IntoIterator<Item = Coordinates<T>>>(mut self, cells: I) -> Self {
+        self.pinned.extend(cells);
+        self.alive.extend(self.pinned.iter().copied());
+        self.changed = None;
+
+        self
+    }
+
+    /// Number of generations computed so far.
+    #[must_use]
+    #[inline]
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        self.alive.contains(pos)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Coordinates<T>> {
+        self.alive.iter()
+    }
+
+    /// Number of living cells.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.alive.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.alive.is_empty()
+    }
+
+    /// The living cells of the current generation.
+    #[must_use]
+    pub fn to_grid(&self) -> BooleanGrid<T> {
+        let mut grid = BooleanGrid::new();
+        self.alive.iter().for_each(|&pos| grid.mark(pos));
+
+        grid
+    }
+
+    /// Computes the next generation, returning whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        // `next` still holds the previous generation, which only differs from
+        // the current one on the cells that last changed. Without those, it
+        // has to be rebuilt in full.
+        let candidates = match self.changed.take() {
+            Some(changed) => {
+                for pos in &changed {
+                    if self.alive.contains(pos) {
+                        self.next.insert(*pos);
+                    } else {
+                        self.next.remove(pos);
+                    }
+                }
+
+                self.around(changed.into_iter())
+            }
+            None => {
+                self.next.clone_from(&self.alive);
+                self.around(self.alive.iter().copied())
+            }
+        };
+
+        let mut changed = vec![];
+
+        for pos in candidates {
+            if self.pinned.contains(&pos) || !within(self.bounds, &pos) {
+                continue;
+            }
+
+            let alive = self.alive.contains(&pos);
+            let count = self
+                .neighborhood
+                .neighbors(pos)
+                .filter(|neighbor| self.alive.contains(neighbor))
+                .count();
+
+            if self.rule.next(alive, count) != alive {
+                if alive {
+                    self.next.remove(&pos);
+                } else {
+                    self.next.insert(pos);
+                }
+                changed.push(pos);
+            }
+        }
+
+        mem::swap(&mut self.alive, &mut self.next);
+        self.generation += 1;
+        let any = !changed.is_empty();
+        self.changed = Some(changed);
+
+        any
+    }
+
+    /// Computes `generations` more generations.
+    pub fn run(&mut self, generations: usize) {
+        let target = self.generation + generations;
+
+        while self.generation < target {
+            if !self.step() {
+                self.generation = target;
+            }
+        }
+    }
+
+    /// Steps until a generation no longer changes, returning the number of
+    /// the first generation of that fixpoint.
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() {}
+
+        self.generation - 1
+    }
+
+    /// Steps until a generation repeats an earlier one, returning the number
+    /// of the first generation in the cycle and the cycle's length. A fixpoint
+    /// is a cycle of length 1.
+    pub fn find_cycle(&mut self) -> (usize, usize) {
+        let mut seen = FxHashMap::default();
+
+        loop {
+            let mut state = self.alive.iter().copied().collect::<Vec<_>>();
+            state.sort_unstable_by_key(|pos| (pos.y(), pos.x()));
+
+            if let Some(&first) = seen.get(&state) {
+                return (first, self.generation - first);
+            }
+
+            seen.insert(state, self.generation);
+            self.step();
+        }
+    }
+
+    /// `cells` and all of their neighbours.
+    fn around<I: Iterator<Item = Coordinates<T>>>(&self, cells: I) -> FxHashSet<Coordinates<T>> {
+        let mut around = FxHashSet::default();
+
+        for pos in cells {
+            around.insert(pos);
+            around.extend(self.neighborhood.neighbors(pos));
+        }
+
+        around
+    }
+}
+
+fn within<T: PrimInt>(
+    bounds: Option<(Coordinates<T>, Coordinates<T>)>,
+    pos: &Coordinates<T>,
+) -> bool {
+    bounds.is_none_or(|(min, max)| {
+        pos.x() >= min.x() && pos.x() <= max.x() && pos.y() >= min.y() && pos.y() <= max.y()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cells(s: &str) -> Vec<Coordinates<i16>> {
+        BooleanGrid::parse(s, |c| c == '#')
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn glider_moves_diagonally() {
+        let glider = ".#.\n..#\n###";
+        let mut automaton = Automaton::new(cells(glider), BirthSurvival::conway());
+
+        automaton.run(4);
+
+        let moved = cells(glider)
+            .into_iter()
+            .map(|pos| pos + Coordinates::new(1, 1))
+            .collect::<FxHashSet<_>>();
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(automaton.iter().copied().collect::<FxHashSet<_>>(), moved);
+    }
+
+    #[test]
+    fn finds_blinker_cycle_and_block_fixpoint() {
+        let mut blinker = Automaton::new(cells("###"), BirthSurvival::conway());
+        let mut block = Automaton::new(cells("##\n##"), BirthSurvival::conway());
+
+        assert_eq!(blinker.find_cycle(), (0, 2));
+        assert_eq!(block.run_until_stable(), 0);
+        assert_eq!(block.len(), 4);
+    }
+
+    #[test]
+    fn bounds_and_pinned_cells() {
+        let mut automaton = Automaton::new(cells("###"), BirthSurvival::conway())
+            .bounded(Coordinates::new(0, 0), Coordinates::new(2, 0))
+            .pinned([Coordinates::new(0, 0)]);
+
+        automaton.run(3);
+
+        assert_eq!(
+            automaton.iter().collect::<Vec<_>>(),
+            vec![&Coordinates::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn closure_rules_peel_cells() {
+        let mut automaton = Automaton::new(cells("###\n###"), |alive, n| alive && n >= 4);
+
+        assert_eq!(automaton.run_until_stable(), 2);
+        assert!(automaton.is_empty());
+    }
+
+    #[test]
+    fn sparse_updates_match_full_recomputation() {
+        let rule = BirthSurvival::new(&[3, 40], &[2, 3]);
+        let mut automaton = Automaton::new(cells(".##\n##.\n.#."), rule);
+        let mut expected = automaton.iter().copied().collect::<FxHashSet<_>>();
+
+        for _ in 0..50 {
+            expected = automaton
+                .around(expected.iter().copied())
+                .into_iter()
+                .filter(|&pos| {
+                    let count = pos
+                        .neighbors_8()
+                        .iter()
+                        .filter(|n| expected.contains(n))
+                        .count();
+
+                    rule.next(expected.contains(&pos), count)
+                })
+                .collect();
+            automaton.step();
+
+            assert_eq!(
+                automaton.iter().copied().collect::<FxHashSet<_>>(),
+                expected
+            );
+        }
+    }
+}
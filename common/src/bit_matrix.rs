@@ -0,0 +1,289 @@
+const BITS: usize = u64::BITS as usize;
+
+/// A matrix over GF(2), each row packed into `u64` words, where adding rows
+/// is a XOR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: usize,
+    columns: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitMatrix {
+    #[must_use]
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let stride = columns.div_ceil(BITS);
+
+        Self {
+            rows,
+            columns,
+            stride,
+            words: vec![0; stride * rows],
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    fn index(&self, row: usize, column: usize) -> (usize, u64) {
+        assert!(row < self.rows && column < self.columns, "out of bounds");
+
+        (row * self.stride + column / BITS, 1 << (column % BITS))
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        let (word, mask) = self.index(row, column);
+
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: bool) {
+        let (word, mask) = self.index(row, column);
+
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.stride..(row + 1) * self.stride]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for w in 0..self.stride {
+                self.words.swap(a * self.stride + w, b * self.stride + w);
+            }
+        }
+    }
+
+    /// `row ^= source`.
+    fn xor_rows(&mut self, row: usize, source: usize) {
+        for w in 0..self.stride {
+            self.words[row * self.stride + w] ^= self.words[source * self.stride + w];
+        }
+    }
+
+    /// The matrix with `column` added as a last column.
+    fn augmented(&self, column: &[bool]) -> Self {
+        assert_eq!(column.len(), self.rows, "column of the wrong length");
+
+        let mut augmented = Self::new(self.rows, self.columns + 1);
+        for (r, &value) in column.iter().enumerate() {
+            let start = r * augmented.stride;
+            augmented.words[start..start + self.stride].copy_from_slice(self.row(r));
+            augmented.set(r, self.columns, value);
+        }
+
+        augmented
+    }
+
+    /// Brings the matrix into reduced row echelon form, returning the column
+    /// of each pivot, row by row.
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = vec![];
+
+        for column in 0..self.columns {
+            let row = pivots.len();
+            let Some(pivot) = (row..self.rows).find(|&r| self.get(r, column)) else {
+                continue;
+            };
+
+            self.swap_rows(row, pivot);
+            for r in (0..self.rows).filter(|&r| r != row) {
+                if self.get(r, column) {
+                    self.xor_rows(r, row);
+                }
+            }
+
+            pivots.push(column);
+            if pivots.len() == self.rows {
+                break;
+            }
+        }
+
+        pivots
+    }
+
+    /// Number of linearly independent rows.
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    /// Every solution of `self * x = rhs`, or `None` if there is none.
+    #[must_use]
+    pub fn solve(&self, rhs: &[bool]) -> Option<BitSolution> {
+        let mut reduced = self.augmented(rhs);
+        let pivots = reduced.row_reduce();
+
+        if pivots.last() == Some(&self.columns) {
+            return None;
+        }
+
+        let stride = self.columns.div_ceil(BITS);
+        let mut particular = vec![0; stride];
+        for (row, &column) in pivots.iter().enumerate() {
+            if reduced.get(row, self.columns) {
+                particular[column / BITS] |= 1 << (column % BITS);
+            }
+        }
+
+        let nullspace = (0..self.columns)
+            .filter(|c| !pivots.contains(c))
+            .map(|f| {
+                let mut basis = vec![0; stride];
+                basis[f / BITS] |= 1 << (f % BITS);
+                for (row, &column) in pivots.iter().enumerate() {
+                    if reduced.get(row, f) {
+                        basis[column / BITS] |= 1 << (column % BITS);
+                    }
+                }
+
+                basis
+            })
+            .collect();
+
+        Some(BitSolution {
+            columns: self.columns,
+            particular,
+            nullspace,
+        })
+    }
+}
+
+/// The solutions of a linear system over GF(2): the particular solution
+/// XORed with any subset of the nullspace basis vectors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSolution {
+    columns: usize,
+    particular: Vec<u64>,
+    nullspace: Vec<Vec<u64>>,
+}
+
+impl BitSolution {
+    fn unpack(&self, words: &[u64]) -> Vec<bool> {
+        (0..self.columns)
+            .map(|c| words[c / BITS] & (1 << (c % BITS)) != 0)
+            .collect()
+    }
+
+    /// The solution where every free variable is unset.
+    #[must_use]
+    pub fn particular(&self) -> Vec<bool> {
+        self.unpack(&self.particular)
+    }
+
+    /// Number of free variables, the solution count being two to that power.
+    #[must_use]
+    #[inline]
+    pub fn free_variables(&self) -> usize {
+        self.nullspace.len()
+    }
+
+    /// The solution with the fewest set variables, trying every combination
+    /// of the nullspace in Gray code order so each step is a single XOR.
+    ///
+    /// # Panics
+    ///
+    /// If there are 64 free variables or more.
+    #[must_use]
+    pub fn min_weight(&self) -> Vec<bool> {
+        assert!(self.nullspace.len() < BITS, "too many free variables");
+
+        let weight = |words: &[u64]| words.iter().map(|w| w.count_ones()).sum::<u32>();
+
+        let mut current = self.particular.clone();
+        let mut best = (weight(&current), current.clone());
+
+        for i in 1..1u64 << self.nullspace.len() {
+            let basis = &self.nullspace[i.trailing_zeros() as usize];
+            current.iter_mut().zip(basis).for_each(|(w, b)| *w ^= b);
+
+            let w = weight(&current);
+            if w < best.0 {
+                best = (w, current.clone());
+            }
+        }
+
+        self.unpack(&best.1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matrix(rows: &[&str]) -> BitMatrix {
+        let mut m = BitMatrix::new(rows.len(), rows[0].len());
+        for (r, row) in rows.iter().enumerate() {
+            for (c, cell) in row.chars().enumerate() {
+                m.set(r, c, cell == '1');
+            }
+        }
+
+        m
+    }
+
+    fn times(m: &BitMatrix, x: &[bool]) -> Vec<bool> {
+        (0..m.rows())
+            .map(|r| (0..m.columns()).filter(|&c| m.get(r, c) && x[c]).count() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn min_weight_matches_brute_force() {
+        let m = matrix(&["000011", "010001", "001110", "110100"]);
+        let rhs = [false, true, true, false];
+        let solution = m.solve(&rhs).unwrap();
+
+        let brute = (0..1u32 << m.columns())
+            .map(|bits| {
+                (0..m.columns())
+                    .map(|c| bits & (1 << c) != 0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|x| times(&m, x) == rhs)
+            .map(|x| x.iter().filter(|&&b| b).count())
+            .min();
+
+        assert_eq!(m.rank(), 4);
+        assert_eq!(solution.free_variables(), 2);
+        assert_eq!(times(&m, &solution.particular()), rhs);
+        assert_eq!(times(&m, &solution.min_weight()), rhs);
+        assert_eq!(
+            Some(solution.min_weight().iter().filter(|&&b| b).count()),
+            brute
+        );
+    }
+
+    #[test]
+    fn wide_and_inconsistent_systems() {
+        let mut wide = BitMatrix::new(2, 100);
+        wide.set(0, 3, true);
+        wide.set(0, 70, true);
+        wide.set(1, 70, true);
+        wide.set(1, 99, true);
+
+        let solution = wide.solve(&[true, false]).unwrap();
+        assert_eq!(times(&wide, &solution.particular()), [true, false]);
+        assert_eq!(solution.free_variables(), 98);
+
+        let m = matrix(&["11", "11"]);
+        assert_eq!(m.solve(&[true, false]), None);
+        assert_eq!(m.rank(), 1);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{Coordinates, DenseGrid, PrefixSums};
+use num_traits::PrimInt;
+
+/// Maps sparse values on one axis onto consecutive indices. Every value gets
+/// its own index and every gap between two values is kept as a single index
+/// standing for all the values it skips, so order and distances survive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AxisCompression<T> {
+    cells: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> AxisCompression<T> {
+    #[must_use]
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut cells = Vec::with_capacity(values.len() * 2);
+        for (i, &value) in values.iter().enumerate() {
+            if let Some(&previous) = i.checked_sub(1).and_then(|i| values.get(i))
+                && value - previous > T::one()
+            {
+                cells.push((previous + T::one(), value - T::one()));
+            }
+
+            cells.push((value, value));
+        }
+
+        Self { cells }
+    }
+
+    /// Number of indices.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Index standing for `value`, or `None` outside of the compressed range.
+    #[must_use]
+    pub fn index(&self, value: T) -> Option<usize> {
+        let i = self.cells.partition_point(|&(_, end)| end < value);
+
+        self.cells
+            .get(i)
+            .filter(|&&(start, _)| start <= value)
+            .map(|_| i)
+    }
+
+    /// Smallest and largest value index `i` stands for.
+    #[must_use]
+    #[inline]
+    pub fn range(&self, i: usize) -> (T, T) {
+        self.cells[i]
+    }
+
+    /// Number of values index `i` stands for.
+    #[must_use]
+    #[inline]
+    pub fn size(&self, i: usize) -> T {
+        let (start, end) = self.cells[i];
+
+        end - start + T::one()
+    }
+}
+
+/// Maps sparse positions onto a small dense grid, compressing each axis with
+/// an [`AxisCompression`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compression<T> {
+    x: AxisCompression<T>,
+    y: AxisCompression<T>,
+}
+
+impl<T: PrimInt> Compression<T> {
+    #[must_use]
+    pub fn new<I: IntoIterator<Item = Coordinates<T>>>(positions: I) -> Self {
+        let (xs, ys): (Vec<_>, Vec<_>) =
+            positions.into_iter().map(|pos| (pos.x(), pos.y())).unzip();
+
+        Self {
+            x: AxisCompression::new(xs),
+            y: AxisCompression::new(ys),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn x(&self) -> &AxisCompression<T> {
+        &self.x
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn y(&self) -> &AxisCompression<T> {
+        &self.y
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.x.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.y.len()
+    }
+
+    /// Dense position standing for `pos`, or `None` outside of the
+    /// compressed area.
+    #[must_use]
+    pub fn compress(&self, pos: Coordinates<T>) -> Option<Coordinates<usize>> {
+        Some(Coordinates::new(
+            self.x.index(pos.x())?,
+            self.y.index(pos.y())?,
+        ))
+    }
+
+    /// Smallest and largest corner of the original rectangle a dense position
+    /// stands for.
+    #[must_use]
+    pub fn decompress(&self, pos: Coordinates<usize>) -> (Coordinates<T>, Coordinates<T>) {
+        let (x1, x2) = self.x.range(pos.x());
+        let (y1, y2) = self.y.range(pos.y());
+
+        (Coordinates::new(x1, y1), Coordinates::new(x2, y2))
+    }
+
+    /// Number of original positions a dense position stands for.
+    #[must_use]
+    pub fn area(&self, pos: Coordinates<usize>) -> T {
+        self.x.size(pos.x()) * self.y.size(pos.y())
+    }
+
+    /// Dense grid covering the compressed area, filled with `fill`.
+    #[must_use]
+    pub fn grid<U: Clone>(&self, fill: U) -> DenseGrid<U> {
+        DenseGrid::new(self.width(), self.height(), fill)
+    }
+
+    /// Prefix sums of the original area covered by the cells of `grid` that
+    /// `include` accepts, so the area of any rectangle can be queried in
+    /// constant time.
+    #[must_use]
+    pub fn area_sums<U, P>(&self, grid: &DenseGrid<U>, include: P) -> PrefixSums<T>
+    where
+        P: Fn(&U) -> bool,
+    {
+        grid.prefix_sums(|pos, val| {
+            if include(val) {
+                self.area(pos)
+            } else {
+                T::zero()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn axis_keeps_order_and_gaps() {
+        let axis = AxisCompression::new([100u32, 3, 7, 8, 3]);
+
+        assert_eq!(axis.len(), 6);
+        assert_eq!(axis.index(3), Some(0));
+        assert_eq!(axis.index(5), Some(1));
+        assert_eq!(axis.index(8), Some(3));
+        assert_eq!(axis.index(50), Some(4));
+        assert_eq!(axis.index(2), None);
+        assert_eq!(axis.index(101), None);
+        assert_eq!(axis.range(4), (9, 99));
+        assert_eq!((0..axis.len()).map(|i| axis.size(i)).sum::<u32>(), 98);
+    }
+
+    #[test]
+    fn area_sums_measure_original_rectangles() {
+        let compression = Compression::new([
+            Coordinates::new(0u64, 0),
+            Coordinates::new(1_000, 10),
+            Coordinates::new(1_000_000, 2_000),
+        ]);
+        let grid = compression.grid(true);
+        let sums = compression.area_sums(&grid, |&inside| inside);
+
+        let a = compression.compress(Coordinates::new(0, 0)).unwrap();
+        let b = compression.compress(Coordinates::new(1_000, 10)).unwrap();
+        let c = compression
+            .compress(Coordinates::new(1_000_000, 2_000))
+            .unwrap();
+
+        assert_eq!(compression.width(), 5);
+        assert_eq!(sums.sum(a, b), 1_001 * 11);
+        assert_eq!(sums.total(), 1_000_001 * 2_001);
+        assert_eq!(
+            compression.decompress(c),
+            (
+                Coordinates::new(1_000_000, 2_000),
+                Coordinates::new(1_000_000, 2_000)
+            )
+        );
+    }
+}
@@ -1,6 +1,8 @@
-use crate::Direction;
-use num_traits::{Float, NumCast, PrimInt, ToPrimitive};
-use std::ops::{Add, AddAssign, Range, RangeInclusive, Sub, SubAssign};
+use crate::Direction8;
+use num_traits::{Euclid, Float, NumCast, PrimInt, Signed, ToPrimitive};
+use std::ops::{
+    Add, AddAssign, Div, Mul, MulAssign, Neg, Range, RangeInclusive, Rem, Sub, SubAssign,
+};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
@@ -185,52 +187,37 @@ where
         }
     }
 
-    pub fn move_next(&mut self, dir: Direction) -> &mut Self {
-        use Direction::*;
-
-        match dir {
-            Up => self.move_up(),
-            Right => self.move_right(),
-            Down => self.move_down(),
-            Left => self.move_left(),
-        };
+    /// Moves one step towards `dir`, which can be a
+    /// [`Direction`](crate::Direction) or a [`Direction8`].
+    pub fn move_next<D: Into<Direction8>>(&mut self, dir: D) -> &mut Self {
+        *self = self.next(dir);
 
         self
     }
 
-    pub fn next(&self, dir: Direction) -> Self {
-        use Direction::*;
+    pub fn next<D: Into<Direction8>>(&self, dir: D) -> Self {
+        use Direction8::*;
 
-        match dir {
+        match dir.into() {
             Up => self.up(),
+            Northeast => self.northeast(),
             Right => self.right(),
+            Southeast => self.southeast(),
             Down => self.down(),
+            Southwest => self.southwest(),
             Left => self.left(),
+            Northwest => self.northwest(),
         }
     }
 
-    pub fn move_previous(&mut self, dir: Direction) -> &mut Self {
-        use Direction::*;
-
-        match dir {
-            Up => self.move_down(),
-            Right => self.move_left(),
-            Down => self.move_up(),
-            Left => self.move_right(),
-        };
+    pub fn move_previous<D: Into<Direction8>>(&mut self, dir: D) -> &mut Self {
+        *self = self.previous(dir);
 
         self
     }
 
-    pub fn previous(&self, dir: Direction) -> Self {
-        use Direction::*;
-
-        match dir {
-            Up => self.down(),
-            Right => self.left(),
-            Down => self.up(),
-            Left => self.right(),
-        }
+    pub fn previous<D: Into<Direction8>>(&self, dir: D) -> Self {
+        self.next(dir.into().opposite())
     }
 
     pub fn neighbors(&self) -> [Coordinates<T>; 4] {
@@ -251,6 +238,148 @@ where
     }
 }
 
+impl<T> Coordinates<T>
+where
+    T: PrimInt,
+{
+    /// Same as [`Self::neighbors`], skipping positions that don't fit in `T`,
+    /// such as those left of `x = 0` for unsigned types.
+    pub fn checked_neighbors(&self) -> impl Iterator<Item = Coordinates<T>> + use<T> {
+        let (x, y, one) = (self.x, self.y, T::one());
+
+        [
+            y.checked_sub(&one).map(|y| Self::new(x, y)),
+            x.checked_add(&one).map(|x| Self::new(x, y)),
+            y.checked_add(&one).map(|y| Self::new(x, y)),
+            x.checked_sub(&one).map(|x| Self::new(x, y)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Same as [`Self::neighbors_8`], skipping positions that don't fit in
+    /// `T`.
+    pub fn checked_neighbors_8(&self) -> impl Iterator<Item = Coordinates<T>> + use<T> {
+        let (x, y, one) = (self.x, self.y, T::one());
+        let (up, down) = (y.checked_sub(&one), y.checked_add(&one));
+        let (left, right) = (x.checked_sub(&one), x.checked_add(&one));
+        let at = |x: Option<T>, y: Option<T>| Some(Self::new(x?, y?));
+
+        [
+            at(Some(x), up),
+            at(right, up),
+            at(right, Some(y)),
+            at(right, down),
+            at(Some(x), down),
+            at(left, down),
+            at(left, Some(y)),
+            at(left, up),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Same as [`Self::neighbors`], clamping positions to the range of `T`,
+    /// so the position itself may be returned.
+    pub fn saturating_neighbors(&self) -> [Coordinates<T>; 4] {
+        let (x, y, one) = (self.x, self.y, T::one());
+
+        [
+            Self::new(x, y.saturating_sub(one)),
+            Self::new(x.saturating_add(one), y),
+            Self::new(x, y.saturating_add(one)),
+            Self::new(x.saturating_sub(one), y),
+        ]
+    }
+
+    /// Same as [`Self::checked_neighbors`], only keeping positions between
+    /// `min` and `max`, both included.
+    pub fn neighbors_within(
+        &self,
+        min: Self,
+        max: Self,
+    ) -> impl Iterator<Item = Coordinates<T>> + use<T> {
+        self.checked_neighbors()
+            .filter(move |pos| pos.is_within(min, max))
+    }
+
+    /// Same as [`Self::checked_neighbors_8`], only keeping positions between
+    /// `min` and `max`, both included.
+    pub fn neighbors_8_within(
+        &self,
+        min: Self,
+        max: Self,
+    ) -> impl Iterator<Item = Coordinates<T>> + use<T> {
+        self.checked_neighbors_8()
+            .filter(move |pos| pos.is_within(min, max))
+    }
+
+    /// Whether the position lies between `min` and `max`, both included.
+    #[inline]
+    pub fn is_within(&self, min: Self, max: Self) -> bool {
+        self.x >= min.x && self.x <= max.x && self.y >= min.y && self.y <= max.y
+    }
+
+    /// Sum of the products of the coordinates.
+    #[inline]
+    pub fn dot(&self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y
+    }
+}
+
+impl<T> Coordinates<T>
+where
+    T: Copy + Signed,
+{
+    /// The sign of each coordinate, turning any offset into a single step
+    /// towards the same direction, diagonals included.
+    #[inline]
+    #[must_use]
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs())
+    }
+}
+
+impl<T> Coordinates<T>
+where
+    T: Copy + Euclid + PrimInt,
+{
+    /// Euclidean remainder of each coordinate, which is never negative.
+    #[must_use]
+    pub fn rem_euclid(&self, rhs: Self) -> Self {
+        Self::new(self.x.rem_euclid(&rhs.x), self.y.rem_euclid(&rhs.y))
+    }
+
+    /// Brings the position back into the area of the given `size` starting at
+    /// `(0, 0)`, as if that area were repeated infinitely in every direction.
+    #[must_use]
+    pub fn wrap(&self, size: Self) -> Self {
+        self.rem_euclid(size)
+    }
+
+    /// Which copy of the repeated area of the given `size` the position lies
+    /// in, the original area being `(0, 0)`.
+    #[must_use]
+    pub fn tile(&self, size: Self) -> Self {
+        Self::new(self.x.div_euclid(&size.x), self.y.div_euclid(&size.y))
+    }
+
+    /// Adds `rhs` to the position, wrapping the result around an area of the
+    /// given `size`.
+    #[must_use]
+    pub fn wrapping_add(&self, rhs: Self, size: Self) -> Self {
+        let (a, b) = (self.wrap(size), rhs.wrap(size));
+
+        Self::new(a.x + b.x, a.y + b.y).wrap(size)
+    }
+}
+
 impl<T, U, V> From<(U, V)> for Coordinates<T>
 where
     T: NumCast,
@@ -335,3 +464,122 @@ impl<T: Sub<Output = T>> Sub for Coordinates<T> {
         }
     }
 }
+
+impl<T: AddAssign> AddAssign for Coordinates<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Coordinates<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Coordinates<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Copy + MulAssign> MulAssign<T> for Coordinates<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Coordinates<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+/// Remainder of each coordinate by the matching one of `rhs`, keeping the
+/// sign of `self` like `%` does. See [`Coordinates::rem_euclid`] otherwise.
+impl<T: Rem<Output = T>> Rem for Coordinates<T> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x % rhs.x,
+            y: self.y % rhs.y,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Coordinates<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let mut pos = Coordinates::new(7i32, -3);
+
+        pos += Coordinates::new(1, 1);
+        pos -= Coordinates::new(0, 3);
+        assert_eq!(pos, Coordinates::new(8, -5));
+        assert_eq!(pos * 3, Coordinates::new(24, -15));
+        assert_eq!(-pos / 2, Coordinates::new(-4, 2));
+        assert_eq!(pos % Coordinates::new(3, 3), Coordinates::new(2, -2));
+        assert_eq!(
+            pos.rem_euclid(Coordinates::new(3, 3)),
+            Coordinates::new(2, 1)
+        );
+        assert_eq!(pos.dot(Coordinates::new(2, 1)), 11);
+        assert_eq!(pos.signum(), Coordinates::new(1, -1));
+
+        pos *= -1;
+        assert_eq!(pos.abs(), Coordinates::new(8, 5));
+    }
+
+    #[test]
+    fn unsigned_neighbors_skip_underflow() {
+        let origin = Coordinates::new(0u8, 0);
+        let corner = Coordinates::new(255u8, 3);
+
+        assert_eq!(
+            origin.checked_neighbors().collect::<Vec<_>>(),
+            vec![Coordinates::new(1, 0), Coordinates::new(0, 1)]
+        );
+        assert_eq!(origin.checked_neighbors_8().count(), 3);
+        assert_eq!(corner.checked_neighbors_8().count(), 5);
+        assert_eq!(origin.saturating_neighbors()[0], origin);
+        assert_eq!(
+            Coordinates::new(2u8, 0)
+                .neighbors_within(Coordinates::new(0, 0), Coordinates::new(2, 2))
+                .collect::<Vec<_>>(),
+            vec![Coordinates::new(2, 1), Coordinates::new(1, 0)]
+        );
+        assert_eq!(
+            Coordinates::new(1usize, 1)
+                .neighbors_8_within(Coordinates::new(0, 0), Coordinates::new(1, 1))
+                .count(),
+            3
+        );
+    }
+}
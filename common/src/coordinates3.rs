@@ -0,0 +1,222 @@
+use crate::coordinates::TryFromStrError;
+use num_traits::PrimInt;
+use std::{
+    array,
+    ops::{Add, Sub},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Coordinates3<T> {
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T> Coordinates3<T> {
+    #[inline]
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Coordinates3<T>
+where
+    T: Copy,
+{
+    #[inline]
+    pub fn x(&self) -> T {
+        self.x
+    }
+
+    #[inline]
+    pub fn y(&self) -> T {
+        self.y
+    }
+
+    #[inline]
+    pub fn z(&self) -> T {
+        self.z
+    }
+}
+
+impl<T> Coordinates3<T>
+where
+    T: PrimInt,
+{
+    /// The six positions sharing a face with this one.
+    pub fn neighbors(&self) -> [Coordinates3<T>; 6] {
+        let one = T::one();
+
+        [
+            Self::new(self.x - one, self.y, self.z),
+            Self::new(self.x + one, self.y, self.z),
+            Self::new(self.x, self.y - one, self.z),
+            Self::new(self.x, self.y + one, self.z),
+            Self::new(self.x, self.y, self.z - one),
+            Self::new(self.x, self.y, self.z + one),
+        ]
+    }
+
+    /// The 26 positions sharing a face, an edge or a corner with this one.
+    pub fn neighbors_26(&self) -> [Coordinates3<T>; 26] {
+        let offset = |v: T, d: usize| match d {
+            0 => v - T::one(),
+            1 => v,
+            _ => v + T::one(),
+        };
+
+        array::from_fn(|i| {
+            // Skip index 13, the position itself.
+            let i = if i < 13 { i } else { i + 1 };
+
+            Self::new(
+                offset(self.x, i % 3),
+                offset(self.y, i / 3 % 3),
+                offset(self.z, i / 9),
+            )
+        })
+    }
+
+    /// Same as [`Self::neighbors`], skipping positions that don't fit in `T`,
+    /// such as those below `0` for unsigned types.
+    pub fn checked_neighbors(&self) -> impl Iterator<Item = Coordinates3<T>> + use<T> {
+        let (x, y, z, one) = (self.x, self.y, self.z, T::one());
+
+        [
+            x.checked_sub(&one).map(|x| Self::new(x, y, z)),
+            x.checked_add(&one).map(|x| Self::new(x, y, z)),
+            y.checked_sub(&one).map(|y| Self::new(x, y, z)),
+            y.checked_add(&one).map(|y| Self::new(x, y, z)),
+            z.checked_sub(&one).map(|z| Self::new(x, y, z)),
+            z.checked_add(&one).map(|z| Self::new(x, y, z)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Same as [`Self::neighbors_26`], skipping positions that don't fit in
+    /// `T`.
+    pub fn checked_neighbors_26(&self) -> impl Iterator<Item = Coordinates3<T>> + use<T> {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let offset = |v: T, d: usize| match d {
+            0 => v.checked_sub(&T::one()),
+            1 => Some(v),
+            _ => v.checked_add(&T::one()),
+        };
+
+        (0..27).filter(|&i| i != 13).filter_map(move |i| {
+            Some(Self::new(
+                offset(x, i % 3)?,
+                offset(y, i / 3 % 3)?,
+                offset(z, i / 9)?,
+            ))
+        })
+    }
+}
+
+impl<T> TryFrom<&str> for Coordinates3<T>
+where
+    T: std::str::FromStr,
+{
+    type Error = TryFromStrError<<T as std::str::FromStr>::Err>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut parts = value.split(',');
+
+        let x = parts.next().ok_or(TryFromStrError::NotEnoughElements)?;
+        let y = parts.next().ok_or(TryFromStrError::NotEnoughElements)?;
+        let z = parts.next().ok_or(TryFromStrError::NotEnoughElements)?;
+
+        let x = T::from_str(x)?;
+        let y = T::from_str(y)?;
+        let z = T::from_str(z)?;
+
+        Ok(Self::new(x, y, z))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Coordinates3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Coordinates3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rustc_hash::FxHashSet;
+
+    #[test]
+    fn parses_comma_separated_values() {
+        let pos = Coordinates3::<i32>::try_from("162,-817,812").unwrap();
+
+        assert_eq!((pos.x(), pos.y(), pos.z()), (162, -817, 812));
+        assert!(Coordinates3::<i32>::try_from("1,2").is_err());
+        assert!(Coordinates3::<u8>::try_from("1,2,-3").is_err());
+    }
+
+    #[test]
+    fn neighbors_are_distinct_and_adjacent() {
+        let pos = Coordinates3::new(5i8, -3, 0);
+        let faces = pos.neighbors().into_iter().collect::<FxHashSet<_>>();
+        let all = pos.neighbors_26().into_iter().collect::<FxHashSet<_>>();
+
+        assert_eq!(faces.len(), 6);
+        assert_eq!(all.len(), 26);
+        assert!(faces.is_subset(&all));
+        assert!(!all.contains(&pos));
+        assert!(all.iter().all(|&n| {
+            let d = n - pos;
+            d.x().abs() <= 1 && d.y().abs() <= 1 && d.z().abs() <= 1
+        }));
+    }
+
+    #[test]
+    fn checked_neighbors_skip_positions_below_unsigned_zero() {
+        let origin = Coordinates3::<u64>::new(0, 0, 0);
+        let boxes = FxHashSet::from_iter([
+            origin,
+            Coordinates3::new(1, 0, 0),
+            Coordinates3::new(1, 1, 1),
+        ]);
+
+        assert_eq!(origin.checked_neighbors().count(), 3);
+        assert_eq!(origin.checked_neighbors_26().count(), 7);
+        assert_eq!(
+            Coordinates3::new(5u64, 5, 5)
+                .checked_neighbors_26()
+                .collect::<Vec<_>>(),
+            Coordinates3::new(5, 5, 5).neighbors_26()
+        );
+        assert_eq!(
+            boxes
+                .iter()
+                .filter(|b| b.checked_neighbors().any(|n| boxes.contains(&n)))
+                .count(),
+            2
+        );
+        assert!(
+            boxes
+                .iter()
+                .all(|b| b.checked_neighbors_26().any(|n| boxes.contains(&n)))
+        );
+    }
+}
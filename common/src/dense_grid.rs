@@ -0,0 +1,173 @@
+use crate::Coordinates;
+use num_traits::Num;
+
+/// A grid of `width * height` values stored row by row, addressed by
+/// positions starting at `(0, 0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<U> {
+    width: usize,
+    height: usize,
+    cells: Vec<U>,
+}
+
+impl<U: Clone> DenseGrid<U> {
+    #[must_use]
+    pub fn new(width: usize, height: usize, fill: U) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<U> DenseGrid<U> {
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn within_bounds(&self, pos: Coordinates<usize>) -> bool {
+        pos.x() < self.width && pos.y() < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: &Coordinates<usize>) -> Option<&U> {
+        self.within_bounds(*pos)
+            .then(|| &self.cells[pos.y() * self.width + pos.x()])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Coordinates<usize>) -> Option<&mut U> {
+        self.within_bounds(pos)
+            .then(|| &mut self.cells[pos.y() * self.width + pos.x()])
+    }
+
+    /// Stores a value, returning `false` if `pos` is out of bounds.
+    pub fn store(&mut self, pos: Coordinates<usize>, value: U) -> bool {
+        self.get_mut(pos).map(|cell| *cell = value).is_some()
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates<usize>, &U)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, val)| (Coordinates::new(i % self.width, i / self.width), val))
+    }
+
+    /// Up, right, down and left neighbours of `pos` that lie within the grid.
+    pub fn neighbors(&self, pos: Coordinates<usize>) -> impl Iterator<Item = Coordinates<usize>> {
+        let (x, y) = (pos.x(), pos.y());
+
+        [
+            y.checked_sub(1).map(|y| Coordinates::new(x, y)),
+            Some(Coordinates::new(x + 1, y)),
+            Some(Coordinates::new(x, y + 1)),
+            x.checked_sub(1).map(|x| Coordinates::new(x, y)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&pos| self.within_bounds(pos))
+    }
+
+    /// Running totals of `f` over the grid, to sum any rectangle in constant
+    /// time.
+    #[must_use]
+    pub fn prefix_sums<V, F>(&self, f: F) -> PrefixSums<V>
+    where
+        V: Copy + Num,
+        F: Fn(Coordinates<usize>, &U) -> V,
+    {
+        let width = self.width + 1;
+        let mut sums = vec![V::zero(); width * (self.height + 1)];
+
+        self.iter().for_each(|(pos, val)| {
+            let (x, y) = (pos.x() + 1, pos.y() + 1);
+
+            sums[y * width + x] = f(pos, val) + sums[(y - 1) * width + x] + sums[y * width + x - 1]
+                - sums[(y - 1) * width + x - 1];
+        });
+
+        PrefixSums { width, sums }
+    }
+}
+
+/// Sums of a [`DenseGrid`] over every rectangle anchored at `(0, 0)`, as
+/// built by [`DenseGrid::prefix_sums`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixSums<V> {
+    width: usize,
+    sums: Vec<V>,
+}
+
+impl<V> PrefixSums<V>
+where
+    V: Copy + Num,
+{
+    /// Sum over the rectangle between corners `a` and `b`, both included.
+    #[must_use]
+    pub fn sum(&self, a: Coordinates<usize>, b: Coordinates<usize>) -> V {
+        let (x1, x2) = (a.x().min(b.x()), a.x().max(b.x()) + 1);
+        let (y1, y2) = (a.y().min(b.y()), a.y().max(b.y()) + 1);
+        let at = |x: usize, y: usize| self.sums[y * self.width + x];
+
+        at(x2, y2) + at(x1, y1) - at(x1, y2) - at(x2, y1)
+    }
+
+    /// Sum over the whole grid.
+    #[must_use]
+    pub fn total(&self) -> V {
+        self.sums.last().copied().unwrap_or_else(V::zero)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prefix_sums_match_brute_force() {
+        let mut grid = DenseGrid::new(5, 4, 0i64);
+        for y in 0..4 {
+            for x in 0..5 {
+                grid.store(Coordinates::new(x, y), (x * 3 + y * 7) as i64 % 5 - 2);
+            }
+        }
+        let sums = grid.prefix_sums(|_, &v| v);
+
+        for (a, _) in grid.iter() {
+            for (b, _) in grid.iter() {
+                let expected = grid
+                    .iter()
+                    .filter(|(p, _)| {
+                        (a.x().min(b.x())..=a.x().max(b.x())).contains(&p.x())
+                            && (a.y().min(b.y())..=a.y().max(b.y())).contains(&p.y())
+                    })
+                    .map(|(_, &v)| v)
+                    .sum::<i64>();
+
+                assert_eq!(sums.sum(a, b), expected);
+            }
+        }
+        assert_eq!(sums.total(), grid.iter().map(|(_, &v)| v).sum::<i64>());
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let mut grid = DenseGrid::new(3, 2, ());
+
+        assert_eq!(grid.neighbors(Coordinates::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbors(Coordinates::new(1, 1)).count(), 3);
+        assert!(!grid.store(Coordinates::new(3, 0), ()));
+    }
+}
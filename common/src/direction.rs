@@ -1,3 +1,8 @@
+use crate::Coordinates;
+use num_traits::Signed;
+use std::str::FromStr;
+use thiserror::Error;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Hash)]
 pub enum Direction {
     #[default]
@@ -8,6 +13,9 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Every direction, clockwise from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
     #[inline]
     #[must_use]
     pub fn clockwise(&self) -> Self {
@@ -59,4 +67,268 @@ impl Direction {
 
         self == &Up || self == &Down
     }
+
+    #[inline]
+    #[must_use]
+    pub fn opposite(&self) -> Self {
+        self.clockwise().clockwise()
+    }
+
+    /// Offset of one step in this direction, `y` growing downwards.
+    #[inline]
+    #[must_use]
+    pub fn delta<T: Signed>(&self) -> Coordinates<T> {
+        Direction8::from(*self).delta()
+    }
+}
+
+/// One of the eight directions a position can move to, including diagonals.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Hash)]
+pub enum Direction8 {
+    #[default]
+    Up,
+    Northeast,
+    Right,
+    Southeast,
+    Down,
+    Southwest,
+    Left,
+    Northwest,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from [`Direction8::Up`].
+    pub const ALL: [Direction8; 8] = [
+        Self::Up,
+        Self::Northeast,
+        Self::Right,
+        Self::Southeast,
+        Self::Down,
+        Self::Southwest,
+        Self::Left,
+        Self::Northwest,
+    ];
+
+    #[inline]
+    fn rotate(&self, eighths: usize) -> Self {
+        Self::ALL[(*self as usize + eighths) % 8]
+    }
+
+    /// Turns 45° clockwise.
+    #[inline]
+    #[must_use]
+    pub fn clockwise(&self) -> Self {
+        self.rotate(1)
+    }
+
+    #[inline]
+    pub fn turn_clockwise(&mut self) {
+        *self = self.clockwise();
+    }
+
+    /// Turns 45° counterclockwise.
+    #[inline]
+    #[must_use]
+    pub fn counterclockwise(&self) -> Self {
+        self.rotate(7)
+    }
+
+    #[inline]
+    pub fn turn_counterclockwise(&mut self) {
+        *self = self.counterclockwise();
+    }
+
+    /// Turns 90° clockwise.
+    #[inline]
+    #[must_use]
+    pub fn right_angle_clockwise(&self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 90° counterclockwise.
+    #[inline]
+    #[must_use]
+    pub fn right_angle_counterclockwise(&self) -> Self {
+        self.rotate(6)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn opposite(&self) -> Self {
+        self.rotate(4)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+
+    /// The up or down part of the direction, if any.
+    #[must_use]
+    pub fn vertical(&self) -> Option<Direction> {
+        use Direction8::*;
+
+        match self {
+            Northwest | Up | Northeast => Some(Direction::Up),
+            Southwest | Down | Southeast => Some(Direction::Down),
+            Left | Right => None,
+        }
+    }
+
+    /// The left or right part of the direction, if any.
+    #[must_use]
+    pub fn horizontal(&self) -> Option<Direction> {
+        use Direction8::*;
+
+        match self {
+            Northeast | Right | Southeast => Some(Direction::Right),
+            Northwest | Left | Southwest => Some(Direction::Left),
+            Up | Down => None,
+        }
+    }
+
+    /// Offset of one step in this direction, `y` growing downwards.
+    #[must_use]
+    pub fn delta<T: Signed>(&self) -> Coordinates<T> {
+        let unit = |dir: Option<Direction>, negative: Direction| match dir {
+            None => T::zero(),
+            Some(dir) if dir == negative => -T::one(),
+            Some(_) => T::one(),
+        };
+
+        Coordinates::new(
+            unit(self.horizontal(), Direction::Left),
+            unit(self.vertical(), Direction::Up),
+        )
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("not a direction: {0:?}")]
+pub struct ParseDirectionError(pub(crate) String);
+
+/// Accepts arrows (`^>v<`), `UDLR` and compass points (`NESW`).
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        use Direction::*;
+
+        match value {
+            '^' | 'U' | 'N' => Ok(Up),
+            '>' | 'R' | 'E' => Ok(Right),
+            'v' | 'D' | 'S' => Ok(Down),
+            '<' | 'L' | 'W' => Ok(Left),
+            _ => Err(ParseDirectionError(value.to_string())),
+        }
+    }
+}
+
+/// Accepts the notations of [`Direction::try_from`], as well as names such as
+/// `up` or `north`, ignoring case.
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Direction::*;
+
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Direction::try_from(c);
+        }
+
+        match s.to_ascii_lowercase().as_str() {
+            "up" | "north" => Ok(Up),
+            "right" | "east" => Ok(Right),
+            "down" | "south" => Ok(Down),
+            "left" | "west" => Ok(Left),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction8 {
+    type Error = ParseDirectionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Direction::try_from(value).map(Self::from)
+    }
+}
+
+/// Accepts everything [`Direction`] does, as well as diagonals written as
+/// compass points (`NE`) or names (`northeast`), ignoring case.
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Direction8::*;
+
+        match s.to_ascii_lowercase().as_str() {
+            "ne" | "northeast" => Ok(Northeast),
+            "se" | "southeast" => Ok(Southeast),
+            "sw" | "southwest" => Ok(Southwest),
+            "nw" | "northwest" => Ok(Northwest),
+            _ => Direction::from_str(s).map(Self::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_common_notations() {
+        let parse = |s: &str| {
+            s.chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        assert_eq!(parse("^>v<"), parse("URDL"));
+        assert_eq!(parse("NESW"), Ok(Direction::ALL.to_vec()));
+        assert!(Direction::try_from('x').is_err());
+        assert_eq!("West".parse(), Ok(Direction::Left));
+        assert_eq!("nw".parse(), Ok(Direction8::Northwest));
+        assert_eq!("v".parse(), Ok(Direction8::Down));
+        assert!("NE".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn turns_and_deltas() {
+        for dir in Direction8::ALL {
+            assert_eq!(dir.clockwise().counterclockwise(), dir);
+            assert_eq!(dir.right_angle_clockwise(), dir.clockwise().clockwise());
+            assert_eq!(
+                dir.opposite().delta::<i8>(),
+                Coordinates::new(0, 0) - dir.delta()
+            );
+        }
+        for dir in Direction::ALL {
+            assert_eq!(
+                Direction8::from(dir).right_angle_clockwise(),
+                dir.clockwise().into()
+            );
+            assert_eq!(dir.opposite().opposite(), dir);
+        }
+
+        assert_eq!(
+            Direction8::Southwest.delta::<i32>(),
+            Coordinates::new(-1, 1)
+        );
+        assert_eq!(Direction::Up.delta::<i32>(), Coordinates::new(0, -1));
+        assert!(Direction8::Northeast.is_diagonal());
+    }
 }
@@ -0,0 +1,150 @@
+use crate::Coordinates;
+use num_traits::PrimInt;
+use rustc_hash::FxHashMap;
+use std::{collections::VecDeque, hash::Hash};
+
+/// Where a breadth-first search stores the distances it discovers.
+pub(crate) trait DistanceStore<T> {
+    fn distance(&self, pos: &Coordinates<T>) -> Option<usize>;
+
+    /// Stores a distance, returning `false` if the position can't be held.
+    fn record(&mut self, pos: Coordinates<T>, distance: usize) -> bool;
+}
+
+impl<T: Eq + Hash> DistanceStore<T> for FxHashMap<Coordinates<T>, usize> {
+    #[inline]
+    fn distance(&self, pos: &Coordinates<T>) -> Option<usize> {
+        self.get(pos).copied()
+    }
+
+    #[inline]
+    fn record(&mut self, pos: Coordinates<T>, distance: usize) -> bool {
+        self.insert(pos, distance);
+
+        true
+    }
+}
+
+/// Fills `store` with the number of steps needed to reach every position from
+/// the closest of the `sources`, moving through `neighbors`.
+pub(crate) fn fill_distances<T, D, S, N, I>(store: &mut D, sources: S, neighbors: N)
+where
+    T: Copy,
+    D: DistanceStore<T>,
+    S: IntoIterator<Item = Coordinates<T>>,
+    N: Fn(Coordinates<T>) -> I,
+    I: IntoIterator<Item = Coordinates<T>>,
+{
+    let mut q = VecDeque::new();
+
+    sources.into_iter().for_each(|pos| {
+        if store.distance(&pos).is_none() && store.record(pos, 0) {
+            q.push_back((pos, 0));
+        }
+    });
+
+    while let Some((pos, distance)) = q.pop_front() {
+        for neighbor in neighbors(pos) {
+            if store.distance(&neighbor).is_none() && store.record(neighbor, distance + 1) {
+                q.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+}
+
+/// Distances stored densely for every position within a rectangle, which is
+/// cheaper to build and query than a hash map on grids that are mostly open.
+#[derive(Debug, Clone)]
+pub struct DistanceMap<T> {
+    min: Coordinates<T>,
+    max: Coordinates<T>,
+    width: usize,
+    distances: Vec<Option<usize>>,
+}
+
+impl<T> DistanceMap<T>
+where
+    T: PrimInt,
+{
+    pub(crate) fn new(bounds: Option<(Coordinates<T>, Coordinates<T>)>) -> Self {
+        let zero = Coordinates::new(T::zero(), T::zero());
+        let (min, max) = bounds.unwrap_or((zero, zero));
+        let (width, height) = match bounds {
+            Some(_) => (
+                (max.x() - min.x()).to_usize().unwrap() + 1,
+                (max.y() - min.y()).to_usize().unwrap() + 1,
+            ),
+            None => (0, 0),
+        };
+
+        Self {
+            min,
+            max,
+            width,
+            distances: vec![None; width * height],
+        }
+    }
+
+    fn index(&self, pos: &Coordinates<T>) -> Option<usize> {
+        if pos.x() < self.min.x()
+            || pos.x() > self.max.x()
+            || pos.y() < self.min.y()
+            || pos.y() > self.max.y()
+        {
+            return None;
+        }
+
+        let x = (pos.x() - self.min.x()).to_usize()?;
+        let y = (pos.y() - self.min.y()).to_usize()?;
+
+        Some(y * self.width + x)
+    }
+
+    /// Number of steps to `pos`, or `None` if it can't be reached.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, pos: &Coordinates<T>) -> Option<usize> {
+        self.index(pos).and_then(|i| self.distances[i])
+    }
+
+    /// Every reachable position along with its distance.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates<T>, usize)> + '_ {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, distance)| {
+                let x = T::from(i % self.width)?;
+                let y = T::from(i / self.width)?;
+
+                distance.map(|d| (Coordinates::new(self.min.x() + x, self.min.y() + y), d))
+            })
+    }
+
+    /// Number of reachable positions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.distances.iter().flatten().count()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.distances.iter().all(Option::is_none)
+    }
+}
+
+impl<T> DistanceStore<T> for DistanceMap<T>
+where
+    T: PrimInt,
+{
+    #[inline]
+    fn distance(&self, pos: &Coordinates<T>) -> Option<usize> {
+        self.get(pos)
+    }
+
+    #[inline]
+    fn record(&mut self, pos: Coordinates<T>, distance: usize) -> bool {
+        self.index(&pos)
+            .map(|i| self.distances[i] = Some(distance))
+            .is_some()
+    }
+}
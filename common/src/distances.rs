@@ -1,23 +1,12 @@
-use crate::Coordinates;
-use num_traits::{Float, Num};
+use crate::{Coordinates, Coordinates3};
+use num_traits::{Float, Num, PrimInt, Signed};
+use std::iter::{once, successors};
 
 pub fn manhattan<T>(a: Coordinates<T>, b: Coordinates<T>) -> T
 where
     T: Copy + Num + PartialOrd,
 {
-    let x = if a.x() > b.x() {
-        a.x() - b.x()
-    } else {
-        b.x() - a.x()
-    };
-
-    let y = if a.y() > b.y() {
-        a.y() - b.y()
-    } else {
-        b.y() - a.y()
-    };
-
-    x + y
+    axis_distance(a.x(), b.x()) + axis_distance(a.y(), b.y())
 }
 
 pub fn euclidian<T>(a: Coordinates<T>, b: Coordinates<T>) -> T
@@ -27,9 +16,138 @@ where
     ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)).sqrt()
 }
 
+/// Largest of the distances along each axis, the number of king moves
+/// between the two positions.
+pub fn chebyshev<T>(a: Coordinates<T>, b: Coordinates<T>) -> T
+where
+    T: Copy + Num + PartialOrd,
+{
+    let (x, y) = (axis_distance(a.x(), b.x()), axis_distance(a.y(), b.y()));
+
+    if x > y { x } else { y }
+}
+
+/// Square of the euclidian distance, which orders positions the same way
+/// while staying exact for integers.
+pub fn squared_euclidian<T>(a: Coordinates<T>, b: Coordinates<T>) -> T
+where
+    T: Copy + Num + PartialOrd,
+{
+    let (x, y) = (axis_distance(a.x(), b.x()), axis_distance(a.y(), b.y()));
+
+    x * x + y * y
+}
+
+/// Every position at a manhattan distance of at most `radius` from `center`,
+/// the center included, row by row.
+pub fn within_manhattan<T>(
+    center: Coordinates<T>,
+    radius: T,
+) -> impl Iterator<Item = Coordinates<T>>
+where
+    T: PrimInt + Signed,
+{
+    span(-radius, radius).flat_map(move |dy| {
+        let reach = radius - dy.abs();
+
+        span(-reach, reach).map(move |dx| center + Coordinates::new(dx, dy))
+    })
+}
+
+/// Every position at a manhattan distance of exactly `radius` from
+/// `center`, going clockwise from the top. A radius of zero yields the
+/// center.
+pub fn manhattan_ring<T>(center: Coordinates<T>, radius: T) -> impl Iterator<Item = Coordinates<T>>
+where
+    T: PrimInt + Signed,
+{
+    let r = radius.abs();
+    let side = span(T::zero(), r - T::one());
+    let sides = side
+        .clone()
+        .map(move |i| Coordinates::new(i, i - r))
+        .chain(side.clone().map(move |i| Coordinates::new(r - i, i)))
+        .chain(side.clone().map(move |i| Coordinates::new(-i, r - i)))
+        .chain(side.map(move |i| Coordinates::new(i - r, -i)));
+
+    once(center)
+        .filter(move |_| r.is_zero())
+        .chain(sides.map(move |offset| center + offset))
+}
+
+/// Every position at a Chebyshev distance of at most `radius` from
+/// `center`, the center included, row by row.
+pub fn within_chebyshev<T>(
+    center: Coordinates<T>,
+    radius: T,
+) -> impl Iterator<Item = Coordinates<T>>
+where
+    T: PrimInt + Signed,
+{
+    span(-radius, radius)
+        .flat_map(move |dy| span(-radius, radius).map(move |dx| center + Coordinates::new(dx, dy)))
+}
+
+/// Every position at a Chebyshev distance of exactly `radius` from
+/// `center`, going clockwise from the top left corner. A radius of zero
+/// yields the center.
+pub fn chebyshev_ring<T>(center: Coordinates<T>, radius: T) -> impl Iterator<Item = Coordinates<T>>
+where
+    T: PrimInt + Signed,
+{
+    let r = radius.abs();
+    let side = span(-r, r - T::one());
+    let sides = side
+        .clone()
+        .map(move |i| Coordinates::new(i, -r))
+        .chain(side.clone().map(move |i| Coordinates::new(r, i)))
+        .chain(side.clone().map(move |i| Coordinates::new(-i, r)))
+        .chain(side.map(move |i| Coordinates::new(-r, -i)));
+
+    once(center)
+        .filter(move |_| r.is_zero())
+        .chain(sides.map(move |offset| center + offset))
+}
+
+/// Every value from `min` to `max`, both included.
+pub(crate) fn span<T: PrimInt>(min: T, max: T) -> impl Iterator<Item = T> + Clone {
+    successors(Some(min), move |&v| (v < max).then(|| v + T::one())).take_while(move |&v| v <= max)
+}
+
+/// Distance along one axis, without going below zero for unsigned types.
+fn axis_distance<T>(a: T, b: T) -> T
+where
+    T: Copy + Num + PartialOrd,
+{
+    if a > b { a - b } else { b - a }
+}
+
+pub fn manhattan_3d<T>(a: Coordinates3<T>, b: Coordinates3<T>) -> T
+where
+    T: Copy + Num + PartialOrd,
+{
+    axis_distance(a.x(), b.x()) + axis_distance(a.y(), b.y()) + axis_distance(a.z(), b.z())
+}
+
+/// Square of the euclidian distance, which orders positions the same way
+/// while staying exact for integers.
+pub fn squared_euclidian_3d<T>(a: Coordinates3<T>, b: Coordinates3<T>) -> T
+where
+    T: Copy + Num + PartialOrd,
+{
+    let (x, y, z) = (
+        axis_distance(a.x(), b.x()),
+        axis_distance(a.y(), b.y()),
+        axis_distance(a.z(), b.z()),
+    );
+
+    x * x + y * y + z * z
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use rustc_hash::FxHashSet;
 
     #[test]
     fn manhattan_works_with_unsigned() {
@@ -78,4 +196,83 @@ mod test {
 
         assert_eq!(euclidian(a, b), 13705.0f64.sqrt());
     }
+
+    #[test]
+    fn distances_3d_work_with_unsigned() {
+        let a = Coordinates3::new(162u64, 817, 812);
+        let b = Coordinates3::new(425, 690, 689);
+
+        assert_eq!(manhattan_3d(a, b), 263 + 127 + 123);
+        assert_eq!(
+            squared_euclidian_3d(a, b),
+            263 * 263 + 127 * 127 + 123 * 123
+        );
+    }
+
+    #[test]
+    fn chebyshev_and_squared_euclidian() {
+        let a = Coordinates::new(3u32, 10);
+        let b = Coordinates::new(7, 2);
+
+        assert_eq!(chebyshev(a, b), 8);
+        assert_eq!(squared_euclidian(a, b), 80);
+    }
+
+    #[test]
+    fn lattice_points_within_radius() {
+        let center = Coordinates::new(5i32, -2);
+
+        for radius in 0..6 {
+            let within = within_manhattan(center, radius).collect::<Vec<_>>();
+            let ring = manhattan_ring(center, radius).collect::<FxHashSet<_>>();
+
+            assert_eq!(within.len() as i32, 2 * radius * (radius + 1) + 1);
+            assert!(within.iter().all(|&p| manhattan(center, p) <= radius));
+            assert_eq!(ring.len() as i32, (4 * radius).max(1));
+            assert!(ring.iter().all(|&p| manhattan(center, p) == radius));
+
+            let square = within_chebyshev(center, radius).collect::<Vec<_>>();
+            let ring = chebyshev_ring(center, radius).collect::<FxHashSet<_>>();
+
+            assert_eq!(square.len() as i32, (2 * radius + 1).pow(2));
+            assert!(square.iter().all(|&p| chebyshev(center, p) <= radius));
+            assert_eq!(ring.len() as i32, (8 * radius).max(1));
+            assert!(ring.iter().all(|&p| chebyshev(center, p) == radius));
+        }
+    }
+
+    #[test]
+    fn rings_go_clockwise() {
+        let offsets = |ring: Vec<Coordinates<i8>>| {
+            ring.into_iter().map(|p| (p.x(), p.y())).collect::<Vec<_>>()
+        };
+        let center = Coordinates::new(0i8, 0);
+
+        assert_eq!(
+            offsets(manhattan_ring(center, 2).collect()),
+            [
+                (0, -2),
+                (1, -1),
+                (2, 0),
+                (1, 1),
+                (0, 2),
+                (-1, 1),
+                (-2, 0),
+                (-1, -1)
+            ]
+        );
+        assert_eq!(
+            offsets(chebyshev_ring(center, 1).collect()),
+            [
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0)
+            ]
+        );
+    }
 }
@@ -0,0 +1,298 @@
+use crate::{Coordinates, Rectangle, math::gcd};
+use num_traits::{PrimInt, Signed};
+use std::cmp::Ordering;
+
+/// Where a point lies relative to a [`Polygon`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A straight segment between two lattice points, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    start: Coordinates<T>,
+    end: Coordinates<T>,
+}
+
+impl<T> Segment<T> {
+    #[inline]
+    pub fn new(start: Coordinates<T>, end: Coordinates<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T: PrimInt> Segment<T> {
+    #[inline]
+    pub fn start(&self) -> Coordinates<T> {
+        self.start
+    }
+
+    #[inline]
+    pub fn end(&self) -> Coordinates<T> {
+        self.end
+    }
+
+    #[inline]
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y() == self.end.y()
+    }
+
+    #[inline]
+    pub fn is_vertical(&self) -> bool {
+        self.start.x() == self.end.x()
+    }
+
+    #[inline]
+    pub fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    /// Smallest rectangle containing the segment.
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        Rectangle::new(self.start, self.end)
+    }
+
+    /// The part two axis-aligned segments have in common, which may be a
+    /// single point. Returns `None` if they don't meet or if either segment
+    /// is diagonal.
+    pub fn axis_intersection(&self, other: &Self) -> Option<Self> {
+        if !self.is_axis_aligned() || !other.is_axis_aligned() {
+            return None;
+        }
+
+        self.bounding_box()
+            .intersection(&other.bounding_box())
+            .map(|shared| Self::new(shared.min(), shared.max()))
+    }
+}
+
+impl<T: PrimInt + Signed> Segment<T> {
+    /// Number of lattice points on the segment, both ends included.
+    pub fn lattice_points(&self) -> T {
+        let d = self.end - self.start;
+
+        gcd(d.x().abs(), d.y().abs()) + T::one()
+    }
+
+    /// Whether `pos` lies on the segment.
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        orientation(self.start, self.end, *pos) == Ordering::Equal
+            && self.bounding_box().contains(pos)
+    }
+
+    /// Whether the segments share at least one point, touching included.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+        let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+
+        o1 != o2 && o3 != o4
+            || self.contains(&c)
+            || self.contains(&d)
+            || other.contains(&a)
+            || other.contains(&b)
+    }
+
+    /// Whether the segments cross at a single point lying strictly inside
+    /// both of them, so touching ends and overlaps don't count.
+    pub fn crosses(&self, other: &Self) -> bool {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+
+        orientation(a, b, c) == orientation(a, b, d).reverse()
+            && orientation(c, d, a) == orientation(c, d, b).reverse()
+            && orientation(a, b, c) != Ordering::Equal
+            && orientation(c, d, a) != Ordering::Equal
+    }
+}
+
+/// Which way `c` turns from the line going from `a` to `b`: `Greater` for one
+/// side, `Less` for the other, `Equal` when the three points are collinear.
+fn orientation<T: PrimInt + Signed>(
+    a: Coordinates<T>,
+    b: Coordinates<T>,
+    c: Coordinates<T>,
+) -> Ordering {
+    let (ab, ac) = (b - a, c - a);
+
+    (ab.x() * ac.y()).cmp(&(ab.y() * ac.x()))
+}
+
+/// A simple polygon on the lattice, given by its vertices in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<T> {
+    vertices: Vec<Coordinates<T>>,
+}
+
+impl<T: PrimInt> Polygon<T> {
+    /// Builds a polygon from its vertices. The closing edge is implied, so a
+    /// last vertex repeating the first one is dropped.
+    #[must_use]
+    pub fn new(mut vertices: Vec<Coordinates<T>>) -> Self {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+
+        Self { vertices }
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> &[Coordinates<T>] {
+        &self.vertices
+    }
+
+    /// Every edge, the closing one last.
+    pub fn edges(&self) -> impl Iterator<Item = Segment<T>> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| Segment::new(a, b))
+    }
+}
+
+impl<T: PrimInt + Signed> Polygon<T> {
+    /// Twice the signed area, using the shoelace formula. It is positive when
+    /// the vertices turn clockwise on screen, with `y` growing downwards, and
+    /// is always an integer even when the area isn't.
+    pub fn twice_signed_area(&self) -> T {
+        self.edges().fold(T::zero(), |sum, edge| {
+            let (a, b) = (edge.start(), edge.end());
+
+            sum + a.x() * b.y() - b.x() * a.y()
+        })
+    }
+
+    /// Area enclosed by the polygon, rounded down to an integer.
+    pub fn area(&self) -> T {
+        self.twice_signed_area().abs() / (T::one() + T::one())
+    }
+
+    /// Number of lattice points on the edges.
+    pub fn boundary_points(&self) -> T {
+        self.edges().fold(T::zero(), |sum, edge| {
+            sum + edge.lattice_points() - T::one()
+        })
+    }
+
+    /// Number of lattice points strictly inside, using Pick's theorem.
+    pub fn interior_points(&self) -> T {
+        let two = T::one() + T::one();
+
+        (self.twice_signed_area().abs() - self.boundary_points() + two) / two
+    }
+
+    /// Whether `pos` is inside, on the boundary or outside, casting a ray
+    /// towards increasing `x` and counting the edges it crosses.
+    pub fn locate(&self, pos: &Coordinates<T>) -> Location {
+        if self.edges().any(|edge| edge.contains(pos)) {
+            return Location::Boundary;
+        }
+
+        let crossings = self
+            .edges()
+            .filter(|edge| {
+                let (a, b) = (edge.start(), edge.end());
+
+                (a.y() > pos.y()) != (b.y() > pos.y())
+                    && orientation(a, b, *pos) == b.y().cmp(&a.y())
+            })
+            .count();
+
+        if crossings % 2 == 1 {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Whether `pos` is inside the polygon or on its boundary.
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        self.locate(pos) != Location::Outside
+    }
+}
+
+impl<T: PrimInt> FromIterator<Coordinates<T>> for Polygon<T> {
+    fn from_iter<I: IntoIterator<Item = Coordinates<T>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn polygon(vertices: &[(i32, i32)]) -> Polygon<i32> {
+        vertices
+            .iter()
+            .map(|&(x, y)| Coordinates::new(x, y))
+            .collect()
+    }
+
+    fn segment(a: (i32, i32), b: (i32, i32)) -> Segment<i32> {
+        Segment::new(Coordinates::new(a.0, a.1), Coordinates::new(b.0, b.1))
+    }
+
+    #[test]
+    fn shoelace_and_pick() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4), (0, 0)]);
+        let triangle = polygon(&[(0, 0), (0, 3), (3, 0)]);
+
+        assert_eq!(square.vertices().len(), 4);
+        assert_eq!(square.twice_signed_area(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(triangle.twice_signed_area(), -9);
+        assert_eq!(triangle.area(), 4);
+        assert_eq!(triangle.interior_points(), 1);
+    }
+
+    #[test]
+    fn locates_points_against_concave_polygon() {
+        let shape = polygon(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ]);
+
+        let brute = Rectangle::new(Coordinates::new(0, 0), Coordinates::new(13, 9))
+            .iter()
+            .filter(|pos| shape.locate(pos) == Location::Inside)
+            .count();
+
+        assert_eq!(shape.locate(&Coordinates::new(11, 4)), Location::Boundary);
+        assert_eq!(shape.locate(&Coordinates::new(9, 3)), Location::Inside);
+        assert_eq!(shape.locate(&Coordinates::new(5, 2)), Location::Outside);
+        assert_eq!(shape.locate(&Coordinates::new(10, 6)), Location::Inside);
+        assert_eq!(shape.locate(&Coordinates::new(0, 3)), Location::Outside);
+        assert_eq!(brute as i32, shape.interior_points());
+    }
+
+    #[test]
+    fn segment_intersections() {
+        let diagonal = segment((0, 0), (4, 4));
+
+        assert!(diagonal.crosses(&segment((0, 4), (4, 0))));
+        assert!(diagonal.intersects(&segment((2, 2), (5, 0))));
+        assert!(!diagonal.crosses(&segment((2, 2), (5, 0))));
+        assert!(diagonal.intersects(&segment((3, 3), (6, 6))));
+        assert!(!diagonal.intersects(&segment((1, 0), (5, 4))));
+        assert_eq!(diagonal.lattice_points(), 5);
+
+        assert_eq!(
+            segment((0, 2), (6, 2)).axis_intersection(&segment((4, 2), (9, 2))),
+            Some(segment((4, 2), (6, 2)))
+        );
+        assert_eq!(
+            segment((0, 2), (6, 2)).axis_intersection(&segment((3, 0), (3, 5))),
+            Some(segment((3, 2), (3, 2)))
+        );
+        assert_eq!(diagonal.axis_intersection(&segment((0, 1), (4, 1))), None);
+    }
+}
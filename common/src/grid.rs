@@ -1,5 +1,10 @@
-use crate::Coordinates;
-use num_traits::{Num, PrimInt};
+use crate::{
+    Coordinates, DistanceMap, Render,
+    distance_map::fill_distances,
+    neighborhood::Neighborhood,
+    search::{self, ShortestPaths},
+};
+use num_traits::{Euclid, Num, NumCast, PrimInt, Zero};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{
     collections::VecDeque,
@@ -7,13 +12,70 @@ use std::{
     ops::{AddAssign, SubAssign},
 };
 
+/// Walks a character map line by line, handing every cell to `f` and
+/// recording the first position of each of the `markers`.
+pub(crate) fn parse_cells<T, F, const N: usize>(
+    s: &str,
+    markers: [char; N],
+    mut f: F,
+) -> [Option<Coordinates<T>>; N]
+where
+    T: Copy + NumCast,
+    F: FnMut(Coordinates<T>, char),
+{
+    let mut found = [None; N];
+
+    s.lines().enumerate().for_each(|(y, line)| {
+        line.chars().enumerate().for_each(|(x, c)| {
+            let pos = (x, y).into();
+
+            if let Some(i) = markers.iter().position(|&m| m == c) {
+                found[i].get_or_insert(pos);
+            }
+
+            f(pos, c);
+        })
+    });
+
+    found
+}
+
+/// Returns the bottom-right corner of a character map, assuming it starts at `(0, 0)`.
+pub(crate) fn text_extent<T: NumCast>(s: &str) -> Coordinates<T> {
+    let width = s
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let height = s.lines().count();
+
+    (width.saturating_sub(1), height.saturating_sub(1)).into()
+}
+
+type Bounds<T> = (Coordinates<T>, Coordinates<T>);
+
+/// Grows `bounds` so that it includes `pos`.
+fn extend_bounds<T>(bounds: Option<Bounds<T>>, pos: Coordinates<T>) -> Bounds<T>
+where
+    T: Copy + PartialOrd,
+{
+    let Some((min, max)) = bounds else {
+        return (pos, pos);
+    };
+
+    let lowest = |a: T, b: T| if b < a { b } else { a };
+    let highest = |a: T, b: T| if b > a { b } else { a };
+
+    (
+        Coordinates::new(lowest(min.x(), pos.x()), lowest(min.y(), pos.y())),
+        Coordinates::new(highest(max.x(), pos.x()), highest(max.y(), pos.y())),
+    )
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Grid<T, U> {
     items: FxHashMap<Coordinates<T>, U>,
-    min_x: T,
-    max_x: T,
-    min_y: T,
-    max_y: T,
+    bounds: Option<Bounds<T>>,
 }
 
 impl<T, U> Grid<T, U>
@@ -42,17 +104,7 @@ where
     }
 
     fn store_min_max(&mut self, pos: Coordinates<T>) {
-        if self.min_x > pos.x() {
-            self.min_x = pos.x();
-        } else if self.max_x < pos.x() {
-            self.max_x = pos.x();
-        }
-
-        if self.min_y > pos.y() {
-            self.min_y = pos.y();
-        } else if self.max_y < pos.y() {
-            self.max_y = pos.y();
-        }
+        self.bounds = Some(extend_bounds(self.bounds, pos));
     }
 
     pub fn store(&mut self, pos: Coordinates<T>, value: U) {
@@ -75,16 +127,26 @@ where
         self.get(pos).is_some()
     }
 
+    /// Smallest and largest corner of the box enclosing every cell, or `None`
+    /// when the grid is empty.
+    #[must_use]
+    #[inline]
+    pub fn bounding_box(&self) -> Option<(Coordinates<T>, Coordinates<T>)> {
+        self.bounds
+    }
+
     #[must_use]
     #[inline]
     pub fn width(&self) -> T {
-        self.max_x - self.min_x + T::one()
+        self.bounds
+            .map_or(T::zero(), |(min, max)| max.x() - min.x() + T::one())
     }
 
     #[must_use]
     #[inline]
     pub fn height(&self) -> T {
-        self.max_y - self.min_y + T::one()
+        self.bounds
+            .map_or(T::zero(), |(min, max)| max.y() - min.y() + T::one())
     }
 
     #[inline]
@@ -92,6 +154,11 @@ where
         self.items.iter()
     }
 
+    #[inline]
+    fn positions(&self) -> impl Iterator<Item = &Coordinates<T>> {
+        self.items.keys()
+    }
+
     #[must_use]
     #[inline]
     pub fn len(&self) -> usize {
@@ -107,16 +174,57 @@ where
     #[must_use]
     #[inline]
     pub fn within_bounds(&self, pos: Coordinates<T>) -> bool {
-        pos.x() >= self.min_x
-            && pos.x() <= self.max_x
-            && pos.y() >= self.min_y
-            && pos.y() <= self.max_y
+        self.bounds.is_some_and(|(min, max)| {
+            pos.x() >= min.x() && pos.x() <= max.x() && pos.y() >= min.y() && pos.y() <= max.y()
+        })
     }
 
+    /// Removes a cell. The bounds are left untouched, call
+    /// [`Self::recompute_bounds`] to shrink them afterwards.
     #[inline]
     pub fn remove(&mut self, pos: &Coordinates<T>) {
         self.items.remove(pos);
     }
+
+    /// Recomputes the bounds from the remaining cells.
+    pub fn recompute_bounds(&mut self) {
+        self.bounds = self
+            .positions()
+            .fold(None, |bounds, &pos| Some(extend_bounds(bounds, pos)));
+    }
+}
+
+impl<T, U> Grid<T, U>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + Num + NumCast,
+    U: Clone + Default,
+{
+    /// Builds a grid from a character map. Characters for which `f` returns
+    /// `None` are left out of the grid.
+    #[must_use]
+    pub fn parse<F: FnMut(char) -> Option<U>>(s: &str, f: F) -> Self {
+        let (grid, []) = Self::parse_with_markers(s, [], f);
+
+        grid
+    }
+
+    /// Same as [`Grid::parse`], but also returns the position of the first
+    /// occurrence of each of the `markers`, in the same order.
+    #[must_use]
+    pub fn parse_with_markers<F: FnMut(char) -> Option<U>, const N: usize>(
+        s: &str,
+        markers: [char; N],
+        mut f: F,
+    ) -> (Self, [Option<Coordinates<T>>; N]) {
+        let mut grid = Self::with_capacity(s.len());
+        let found = parse_cells(s, markers, |pos, c| {
+            if let Some(value) = f(c) {
+                grid.store(pos, value);
+            }
+        });
+
+        (grid, found)
+    }
 }
 
 impl<T, U> Grid<T, U>
@@ -140,12 +248,20 @@ where
     T: Eq + Hash + Default + Copy + PartialOrd + PrimInt + SubAssign + AddAssign,
     U: Clone + Default,
 {
+    /// Renders the grid as text, turning every cell into a character with `f`.
+    #[must_use]
+    pub fn render<'a, F: Fn(&U) -> char + 'a>(&'a self, f: F) -> Render<'a, T> {
+        Render::new(self.bounds, move |pos| self.get(&pos).map(&f))
+    }
+
     #[must_use]
     pub fn bfs<
+        N: Neighborhood<T>,
         E: Fn(Coordinates<T>, &U) -> bool,
         P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
     >(
         &self,
+        neighborhood: &N,
         root: Coordinates<T>,
         is_target: E,
         pred: P,
@@ -157,7 +273,12 @@ where
         explored.insert(root);
 
         while let Some(v) = q.pop_front() {
-            if is_target(v, self.items.get(&v).unwrap()) {
+            // Cells missing from a sparse grid can't be entered.
+            let Some(value) = self.get(&v) else {
+                continue;
+            };
+
+            if is_target(v, value) {
                 let mut path = vec![v];
                 while let Some(&parent) = parents.get(path.last().unwrap()) {
                     path.push(parent);
@@ -167,14 +288,12 @@ where
 
                 return path;
             } else {
-                for neighbor in v.neighbors() {
-                    if self.within_bounds(neighbor)
-                        && !explored.contains(&neighbor)
-                        && pred(
-                            (v, self.get(&v).unwrap()),
-                            (neighbor, self.get(&neighbor).unwrap()),
-                        )
-                    {
+                for neighbor in neighborhood.neighbors(v) {
+                    let Some(next) = self.get(&neighbor) else {
+                        continue;
+                    };
+
+                    if !explored.contains(&neighbor) && pred((v, value), (neighbor, next)) {
                         parents.insert(neighbor, v);
                         explored.insert(neighbor);
                         q.push_back(neighbor);
@@ -188,10 +307,12 @@ where
 
     #[must_use]
     pub fn bfs_all<
+        N: Neighborhood<T>,
         E: Fn(Coordinates<T>, &U) -> bool,
         P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
     >(
         &self,
+        neighborhood: &N,
         root: Coordinates<T>,
         is_target: E,
         pred: P,
@@ -210,18 +331,19 @@ where
 
         while let Some(path) = q.pop_front() {
             let front = *path.last().unwrap();
+            let Some(value) = self.get(&front) else {
+                continue;
+            };
 
             if ends.contains(&front) {
                 paths.push(path);
             } else {
-                for neighbor in front.neighbors() {
-                    if self.within_bounds(neighbor)
-                        && !explored.contains(&neighbor)
-                        && pred(
-                            (front, self.get(&front).unwrap()),
-                            (neighbor, self.get(&neighbor).unwrap()),
-                        )
-                    {
+                for neighbor in neighborhood.neighbors(front) {
+                    let Some(next) = self.get(&neighbor) else {
+                        continue;
+                    };
+
+                    if !explored.contains(&neighbor) && pred((front, value), (neighbor, next)) {
                         explored.insert(neighbor);
                         let mut path = path.clone();
                         path.push(neighbor);
@@ -236,10 +358,12 @@ where
 
     #[must_use]
     pub fn bfs_exhaustive<
+        N: Neighborhood<T>,
         E: Fn(Coordinates<T>, &U) -> bool,
         P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
     >(
         &self,
+        neighborhood: &N,
         root: Coordinates<T>,
         is_target: E,
         pred: P,
@@ -256,17 +380,19 @@ where
 
         while let Some(path) = q.pop_front() {
             let front = *path.last().unwrap();
+            let Some(value) = self.get(&front) else {
+                continue;
+            };
 
             if ends.contains(&front) {
                 paths.push(path);
             } else {
-                for neighbor in front.neighbors() {
-                    if self.within_bounds(neighbor)
-                        && pred(
-                            (front, self.get(&front).unwrap()),
-                            (neighbor, self.get(&neighbor).unwrap()),
-                        )
-                    {
+                for neighbor in neighborhood.neighbors(front) {
+                    let Some(next) = self.get(&neighbor) else {
+                        continue;
+                    };
+
+                    if pred((front, value), (neighbor, next)) {
                         let mut path = path.clone();
                         path.push(neighbor);
                         q.push_back(path);
@@ -278,9 +404,75 @@ where
         paths
     }
 
+    /// Cheapest path from `root` to a cell accepted by `is_target`. `cost`
+    /// returns the price of moving between two neighbouring cells, or `None`
+    /// when the move isn't allowed.
+    #[must_use]
+    pub fn dijkstra<N, C, E, P>(
+        &self,
+        neighborhood: &N,
+        root: Coordinates<T>,
+        is_target: E,
+        cost: P,
+    ) -> Option<(C, Vec<Coordinates<T>>)>
+    where
+        N: Neighborhood<T>,
+        C: Copy + Ord + Zero,
+        E: Fn(Coordinates<T>, &U) -> bool,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> Option<C>,
+    {
+        search::dijkstra(
+            root,
+            |&pos| self.weighted_neighbors(neighborhood, pos, &cost),
+            |pos| self.get(pos).is_some_and(|val| is_target(*pos, val)),
+        )
+    }
+
+    /// Same as [`Grid::dijkstra`], but keeps every cheapest path to the
+    /// closest targets.
+    #[must_use]
+    pub fn dijkstra_all<N, C, E, P>(
+        &self,
+        neighborhood: &N,
+        root: Coordinates<T>,
+        is_target: E,
+        cost: P,
+    ) -> Option<ShortestPaths<Coordinates<T>, C>>
+    where
+        N: Neighborhood<T>,
+        C: Copy + Ord + Zero,
+        E: Fn(Coordinates<T>, &U) -> bool,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> Option<C>,
+    {
+        search::dijkstra_all(
+            root,
+            |&pos| self.weighted_neighbors(neighborhood, pos, &cost),
+            |pos| self.get(pos).is_some_and(|val| is_target(*pos, val)),
+        )
+    }
+
+    fn weighted_neighbors<'a, C, N, P>(
+        &'a self,
+        neighborhood: &'a N,
+        pos: Coordinates<T>,
+        cost: &'a P,
+    ) -> impl Iterator<Item = (Coordinates<T>, C)> + 'a
+    where
+        N: Neighborhood<T>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> Option<C>,
+    {
+        let current = self.get(&pos).map(|val| (pos, val));
+
+        neighborhood.neighbors(pos).filter_map(move |neighbor| {
+            let next = (neighbor, self.get(&neighbor)?);
+            cost(current?, next).map(|c| (neighbor, c))
+        })
+    }
+
     #[must_use]
-    pub fn flood<P: Fn(&U, &U) -> bool>(
+    pub fn flood<N: Neighborhood<T>, P: Fn(&U, &U) -> bool>(
         &self,
+        neighborhood: &N,
         start: Coordinates<T>,
         pred: P,
     ) -> FxHashSet<Coordinates<T>> {
@@ -293,7 +485,7 @@ where
                 if !plain.contains(&pos) {
                     plain.insert(pos);
 
-                    queue.extend(pos.neighbors().iter().filter(|&pos| {
+                    queue.extend(neighborhood.neighbors(pos).filter(|pos| {
                         if let Some(current_val) = self.items.get(pos) {
                             pred(start_val, current_val)
                         } else {
@@ -306,15 +498,119 @@ where
 
         plain
     }
+
+    /// Number of steps from the closest of the `sources` to every reachable
+    /// cell, moving between neighbours accepted by `pred`.
+    #[must_use]
+    pub fn distances_from<N, I, P>(
+        &self,
+        neighborhood: &N,
+        sources: I,
+        pred: P,
+    ) -> FxHashMap<Coordinates<T>, usize>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        let mut distances = FxHashMap::default();
+        fill_distances(&mut distances, sources, |pos| {
+            self.moves(neighborhood, pos, &pred, false)
+        });
+
+        distances
+    }
+
+    /// Number of steps from every cell to the closest of the `targets`,
+    /// moving between neighbours accepted by `pred`.
+    #[must_use]
+    pub fn distances_to<N, I, P>(
+        &self,
+        neighborhood: &N,
+        targets: I,
+        pred: P,
+    ) -> FxHashMap<Coordinates<T>, usize>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        let mut distances = FxHashMap::default();
+        fill_distances(&mut distances, targets, |pos| {
+            self.moves(neighborhood, pos, &pred, true)
+        });
+
+        distances
+    }
+
+    /// Same as [`Grid::distances_from`], stored densely over the bounds of
+    /// the grid.
+    #[must_use]
+    pub fn distance_map_from<N, I, P>(
+        &self,
+        neighborhood: &N,
+        sources: I,
+        pred: P,
+    ) -> DistanceMap<T>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        let mut distances = DistanceMap::new(self.bounds);
+        fill_distances(&mut distances, sources, |pos| {
+            self.moves(neighborhood, pos, &pred, false)
+        });
+
+        distances
+    }
+
+    /// Same as [`Grid::distances_to`], stored densely over the bounds of the
+    /// grid.
+    #[must_use]
+    pub fn distance_map_to<N, I, P>(&self, neighborhood: &N, targets: I, pred: P) -> DistanceMap<T>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        let mut distances = DistanceMap::new(self.bounds);
+        fill_distances(&mut distances, targets, |pos| {
+            self.moves(neighborhood, pos, &pred, true)
+        });
+
+        distances
+    }
+
+    /// Neighbours of `pos` that can be moved to, or moved from when `reverse`
+    /// is set.
+    fn moves<'a, N, P>(
+        &'a self,
+        neighborhood: &'a N,
+        pos: Coordinates<T>,
+        pred: &'a P,
+        reverse: bool,
+    ) -> impl Iterator<Item = Coordinates<T>> + 'a
+    where
+        N: Neighborhood<T>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        let current = self.get(&pos).map(|val| (pos, val));
+
+        neighborhood.neighbors(pos).filter(move |neighbor| {
+            match (current, self.get(neighbor).map(|val| (*neighbor, val))) {
+                (Some(current), Some(next)) if reverse => pred(next, current),
+                (Some(current), Some(next)) => pred(current, next),
+                _ => false,
+            }
+        })
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct BooleanGrid<T> {
     items: FxHashSet<Coordinates<T>>,
-    min_x: T,
-    max_x: T,
-    min_y: T,
-    max_y: T,
+    bounds: Option<Bounds<T>>,
 }
 
 impl<T> BooleanGrid<T>
@@ -336,17 +632,7 @@ where
     }
 
     fn store_min_max(&mut self, pos: Coordinates<T>) {
-        if self.min_x > pos.x() {
-            self.min_x = pos.x();
-        } else if self.max_x < pos.x() {
-            self.max_x = pos.x();
-        }
-
-        if self.min_y > pos.y() {
-            self.min_y = pos.y();
-        } else if self.max_y < pos.y() {
-            self.max_y = pos.y();
-        }
+        self.bounds = Some(extend_bounds(self.bounds, pos));
     }
 
     #[must_use]
@@ -354,16 +640,26 @@ where
         self.items.contains(pos)
     }
 
+    /// Smallest and largest corner of the box enclosing every cell, or `None`
+    /// when the grid is empty.
+    #[must_use]
+    #[inline]
+    pub fn bounding_box(&self) -> Option<(Coordinates<T>, Coordinates<T>)> {
+        self.bounds
+    }
+
     #[must_use]
     #[inline]
     pub fn width(&self) -> T {
-        self.max_x - self.min_x + T::one()
+        self.bounds
+            .map_or(T::zero(), |(min, max)| max.x() - min.x() + T::one())
     }
 
     #[must_use]
     #[inline]
     pub fn height(&self) -> T {
-        self.max_y - self.min_y + T::one()
+        self.bounds
+            .map_or(T::zero(), |(min, max)| max.y() - min.y() + T::one())
     }
 
     #[inline]
@@ -371,6 +667,11 @@ where
         self.items.iter()
     }
 
+    #[inline]
+    fn positions(&self) -> impl Iterator<Item = &Coordinates<T>> {
+        self.items.iter()
+    }
+
     #[must_use]
     #[inline]
     pub fn len(&self) -> usize {
@@ -386,24 +687,125 @@ where
     #[must_use]
     #[inline]
     pub fn within_bounds(&self, pos: Coordinates<T>) -> bool {
-        pos.x() >= self.min_x
-            && pos.x() <= self.max_x
-            && pos.y() >= self.min_y
-            && pos.y() <= self.max_y
+        self.bounds.is_some_and(|(min, max)| {
+            pos.x() >= min.x() && pos.x() <= max.x() && pos.y() >= min.y() && pos.y() <= max.y()
+        })
     }
 
+    /// Removes a cell. The bounds are left untouched, call
+    /// [`Self::recompute_bounds`] to shrink them afterwards.
     #[inline]
     pub fn remove(&mut self, pos: &Coordinates<T>) {
         self.items.remove(pos);
     }
+
+    /// Recomputes the bounds from the remaining cells.
+    pub fn recompute_bounds(&mut self) {
+        self.bounds = self
+            .positions()
+            .fold(None, |bounds, &pos| Some(extend_bounds(bounds, pos)));
+    }
+}
+
+impl<T> BooleanGrid<T>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + Num + NumCast,
+{
+    /// Builds a grid from a character map, marking every cell for which `f`
+    /// returns `true`.
+    #[must_use]
+    pub fn parse<F: FnMut(char) -> bool>(s: &str, f: F) -> Self {
+        let (grid, []) = Self::parse_with_markers(s, [], f);
+
+        grid
+    }
+
+    /// Same as [`BooleanGrid::parse`], but also returns the position of the
+    /// first occurrence of each of the `markers`, in the same order.
+    #[must_use]
+    pub fn parse_with_markers<F: FnMut(char) -> bool, const N: usize>(
+        s: &str,
+        markers: [char; N],
+        mut f: F,
+    ) -> (Self, [Option<Coordinates<T>>; N]) {
+        let mut grid = Self::new();
+        let found = parse_cells(s, markers, |pos, c| {
+            if f(c) {
+                grid.mark(pos);
+            }
+        });
+
+        (grid, found)
+    }
 }
 
 impl<T> BooleanGrid<T>
 where
     T: Eq + Hash + Default + Copy + PartialOrd + PrimInt + SubAssign + AddAssign,
 {
-    pub fn bfs(
+    /// Renders the grid as text, drawing `glyph` for every marked cell.
+    #[must_use]
+    pub fn render(&self, glyph: char) -> Render<'_, T> {
+        Render::new(self.bounds, move |pos| self.contains(&pos).then_some(glyph))
+    }
+
+    /// Number of steps from the closest of the `sources` to every reachable
+    /// position, moving through marked positions if `is_neighbor` is set, or
+    /// unmarked positions within bounds otherwise.
+    #[must_use]
+    pub fn distances_from<N, I>(
         &self,
+        neighborhood: &N,
+        sources: I,
+        is_neighbor: bool,
+    ) -> FxHashMap<Coordinates<T>, usize>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+    {
+        let mut distances = FxHashMap::default();
+        fill_distances(&mut distances, sources, |pos| {
+            self.moves(neighborhood, pos, is_neighbor)
+        });
+
+        distances
+    }
+
+    /// Same as [`BooleanGrid::distances_from`], stored densely over the bounds
+    /// of the grid.
+    #[must_use]
+    pub fn distance_map_from<N, I>(
+        &self,
+        neighborhood: &N,
+        sources: I,
+        is_neighbor: bool,
+    ) -> DistanceMap<T>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+    {
+        let mut distances = DistanceMap::new(self.bounds);
+        fill_distances(&mut distances, sources, |pos| {
+            self.moves(neighborhood, pos, is_neighbor)
+        });
+
+        distances
+    }
+
+    fn moves<'a, N: Neighborhood<T>>(
+        &'a self,
+        neighborhood: &'a N,
+        pos: Coordinates<T>,
+        is_neighbor: bool,
+    ) -> impl Iterator<Item = Coordinates<T>> + 'a {
+        neighborhood.neighbors(pos).filter(move |&neighbor| {
+            self.within_bounds(neighbor) && self.contains(&neighbor) == is_neighbor
+        })
+    }
+
+    pub fn bfs<N: Neighborhood<T>>(
+        &self,
+        neighborhood: &N,
         root: Coordinates<T>,
         target: Coordinates<T>,
         is_neighbor: bool,
@@ -425,7 +827,7 @@ where
 
                 return path;
             } else {
-                for neighbor in v.neighbors() {
+                for neighbor in neighborhood.neighbors(v) {
                     if self.within_bounds(neighbor)
                         && !explored.contains(&neighbor)
                         && self.contains(&neighbor) == is_neighbor
@@ -441,8 +843,12 @@ where
         vec![]
     }
 
-    pub fn bfs_all<P: Fn((Coordinates<T>, bool), (Coordinates<T>, bool)) -> bool>(
+    pub fn bfs_all<
+        N: Neighborhood<T>,
+        P: Fn((Coordinates<T>, bool), (Coordinates<T>, bool)) -> bool,
+    >(
         &self,
+        neighborhood: &N,
         start: Coordinates<T>,
         end: Coordinates<T>,
         pred: P,
@@ -459,7 +865,7 @@ where
             if front == end {
                 paths.push(path);
             } else {
-                for neighbor in front.neighbors() {
+                for neighbor in neighborhood.neighbors(front) {
                     if self.within_bounds(neighbor)
                         && !explored.contains(&neighbor)
                         && pred(
@@ -526,6 +932,12 @@ where
         self.grid.get(pos).is_some()
     }
 
+    #[must_use]
+    #[inline]
+    pub fn bounding_box(&self) -> (Coordinates<T>, Coordinates<T>) {
+        (self.min, self.max)
+    }
+
     #[must_use]
     #[inline]
     pub fn width(&self) -> T {
@@ -570,6 +982,56 @@ where
     }
 }
 
+impl<T, U> BoundedGrid<T, U>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + Num + NumCast,
+    U: Clone + Default,
+{
+    /// Builds a grid from a character map, bounded by the extent of the text.
+    /// Characters for which `f` returns `None` are left out of the grid.
+    #[must_use]
+    pub fn parse<F: FnMut(char) -> Option<U>>(s: &str, f: F) -> Self {
+        let (grid, []) = Self::parse_with_markers(s, [], f);
+
+        grid
+    }
+
+    /// Same as [`BoundedGrid::parse`], but also returns the position of the
+    /// first occurrence of each of the `markers`, in the same order.
+    #[must_use]
+    pub fn parse_with_markers<F: FnMut(char) -> Option<U>, const N: usize>(
+        s: &str,
+        markers: [char; N],
+        f: F,
+    ) -> (Self, [Option<Coordinates<T>>; N]) {
+        let (grid, found) = Grid::parse_with_markers(s, markers, f);
+
+        (
+            Self {
+                grid,
+                min: Coordinates::new(T::zero(), T::zero()),
+                max: text_extent(s),
+            },
+            found,
+        )
+    }
+}
+
+impl<T, U> BoundedGrid<T, U>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + PrimInt,
+    U: Clone + Default,
+{
+    /// Renders the whole bounded area as text, turning every cell into a
+    /// character with `f`.
+    #[must_use]
+    pub fn render<'a, F: Fn(&U) -> char + 'a>(&'a self, f: F) -> Render<'a, T> {
+        Render::new(Some((self.min, self.max)), move |pos| {
+            self.get(&pos).map(&f)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct BooleanBoundedGrid<T> {
     grid: BooleanGrid<T>,
@@ -605,6 +1067,12 @@ where
         self.grid.contains(pos)
     }
 
+    #[must_use]
+    #[inline]
+    pub fn bounding_box(&self) -> (Coordinates<T>, Coordinates<T>) {
+        (self.min, self.max)
+    }
+
     #[must_use]
     #[inline]
     pub fn width(&self) -> T {
@@ -648,3 +1116,571 @@ where
         self.grid.remove(pos);
     }
 }
+
+impl<T> BooleanBoundedGrid<T>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + Num + NumCast,
+{
+    /// Builds a grid from a character map, bounded by the extent of the text.
+    /// Every cell for which `f` returns `true` is marked.
+    #[must_use]
+    pub fn parse<F: FnMut(char) -> bool>(s: &str, f: F) -> Self {
+        let (grid, []) = Self::parse_with_markers(s, [], f);
+
+        grid
+    }
+
+    /// Same as [`BooleanBoundedGrid::parse`], but also returns the position of
+    /// the first occurrence of each of the `markers`, in the same order.
+    #[must_use]
+    pub fn parse_with_markers<F: FnMut(char) -> bool, const N: usize>(
+        s: &str,
+        markers: [char; N],
+        f: F,
+    ) -> (Self, [Option<Coordinates<T>>; N]) {
+        let (grid, found) = BooleanGrid::parse_with_markers(s, markers, f);
+
+        (
+            Self {
+                grid,
+                min: Coordinates::new(T::zero(), T::zero()),
+                max: text_extent(s),
+            },
+            found,
+        )
+    }
+}
+
+impl<T> BooleanBoundedGrid<T>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + PrimInt,
+{
+    /// Renders the whole bounded area as text, drawing `glyph` for every
+    /// marked cell.
+    #[must_use]
+    pub fn render(&self, glyph: char) -> Render<'_, T> {
+        Render::new(Some((self.min, self.max)), move |pos| {
+            self.contains(&pos).then_some(glyph)
+        })
+    }
+}
+
+/// A grid whose edges wrap around, as if it were tiled infinitely in every
+/// direction. Any position can be queried and refers to the cell at the same
+/// place in the original tile.
+#[derive(Debug, Clone, Default)]
+pub struct WrappingGrid<T, U> {
+    grid: Grid<T, U>,
+    size: Coordinates<T>,
+}
+
+impl<T, U> WrappingGrid<T, U>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + PrimInt + Euclid,
+    U: Clone + Default,
+{
+    /// Creates an empty grid whose tile spans `size` cells from `(0, 0)`.
+    #[must_use]
+    #[inline]
+    pub fn new(size: Coordinates<T>) -> Self {
+        Self {
+            grid: Grid::new(),
+            size,
+        }
+    }
+
+    pub fn store(&mut self, pos: Coordinates<T>, value: U) {
+        self.grid.store(self.wrap(pos), value);
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: &Coordinates<T>) -> Option<&U> {
+        self.grid.get(&self.wrap(*pos))
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Coordinates<T>) -> &mut U {
+        self.grid.get_mut(self.wrap(pos))
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        self.get(pos).is_some()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn size(&self) -> Coordinates<T> {
+        self.size
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> T {
+        self.size.x()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> T {
+        self.size.y()
+    }
+
+    /// The position within the original tile that `pos` refers to.
+    #[must_use]
+    #[inline]
+    pub fn wrap(&self, pos: Coordinates<T>) -> Coordinates<T> {
+        pos.wrap(self.size)
+    }
+
+    /// Which copy of the tile `pos` lies in, the original being `(0, 0)`.
+    #[must_use]
+    #[inline]
+    pub fn tile(&self, pos: Coordinates<T>) -> Coordinates<T> {
+        pos.tile(self.size)
+    }
+
+    /// Stored cells of the original tile.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Coordinates<T>, &U)> {
+        self.grid.iter()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.grid.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.grid.is_empty()
+    }
+
+    #[inline]
+    pub fn remove(&mut self, pos: &Coordinates<T>) {
+        self.grid.remove(&self.wrap(*pos));
+    }
+
+    fn wrapped<N: Neighborhood<T>>(
+        &self,
+        neighborhood: &N,
+        pos: Coordinates<T>,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        // Same as `Wrapping`, stepping from the next copy of the tile so
+        // unsigned coordinates don't underflow at `0`.
+        neighborhood
+            .neighbors(self.wrap(pos) + self.size)
+            .map(|neighbor| self.wrap(neighbor))
+    }
+}
+
+impl<T, U> WrappingGrid<T, U>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + PrimInt + Euclid,
+    U: Clone + Default,
+{
+    /// Builds a grid from a character map, whose extent becomes the size of
+    /// the tile. Characters for which `f` returns `None` are left out.
+    #[must_use]
+    pub fn parse<F: FnMut(char) -> Option<U>>(s: &str, f: F) -> Self {
+        let (grid, []) = Self::parse_with_markers(s, [], f);
+
+        grid
+    }
+
+    /// Same as [`WrappingGrid::parse`], but also returns the position of the
+    /// first occurrence of each of the `markers`, in the same order.
+    #[must_use]
+    pub fn parse_with_markers<F: FnMut(char) -> Option<U>, const N: usize>(
+        s: &str,
+        markers: [char; N],
+        f: F,
+    ) -> (Self, [Option<Coordinates<T>>; N]) {
+        let (grid, found) = Grid::parse_with_markers(s, markers, f);
+        let extent = text_extent::<T>(s);
+
+        (
+            Self {
+                grid,
+                size: Coordinates::new(extent.x() + T::one(), extent.y() + T::one()),
+            },
+            found,
+        )
+    }
+}
+
+impl<T, U> WrappingGrid<T, U>
+where
+    T: Eq + Hash + Default + Copy + PartialOrd + PrimInt + Euclid + SubAssign + AddAssign,
+    U: Clone + Default,
+{
+    /// Same as [`Grid::bfs`], stepping across the edges of the tile.
+    #[must_use]
+    pub fn bfs<N, E, P>(
+        &self,
+        neighborhood: &N,
+        root: Coordinates<T>,
+        is_target: E,
+        pred: P,
+    ) -> Vec<Coordinates<T>>
+    where
+        N: Neighborhood<T>,
+        E: Fn(Coordinates<T>, &U) -> bool,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        self.grid.bfs(
+            &|pos| self.wrapped(neighborhood, pos),
+            self.wrap(root),
+            is_target,
+            pred,
+        )
+    }
+
+    /// Same as [`Grid::dijkstra`], stepping across the edges of the tile.
+    #[must_use]
+    pub fn dijkstra<N, C, E, P>(
+        &self,
+        neighborhood: &N,
+        root: Coordinates<T>,
+        is_target: E,
+        cost: P,
+    ) -> Option<(C, Vec<Coordinates<T>>)>
+    where
+        N: Neighborhood<T>,
+        C: Copy + Ord + Zero,
+        E: Fn(Coordinates<T>, &U) -> bool,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> Option<C>,
+    {
+        self.grid.dijkstra(
+            &|pos| self.wrapped(neighborhood, pos),
+            self.wrap(root),
+            is_target,
+            cost,
+        )
+    }
+
+    /// Same as [`Grid::flood`], stepping across the edges of the tile.
+    #[must_use]
+    pub fn flood<N: Neighborhood<T>, P: Fn(&U, &U) -> bool>(
+        &self,
+        neighborhood: &N,
+        start: Coordinates<T>,
+        pred: P,
+    ) -> FxHashSet<Coordinates<T>> {
+        self.grid.flood(
+            &|pos| self.wrapped(neighborhood, pos),
+            self.wrap(start),
+            pred,
+        )
+    }
+
+    /// Same as [`Grid::distances_from`], stepping across the edges of the tile.
+    #[must_use]
+    pub fn distances_from<N, I, P>(
+        &self,
+        neighborhood: &N,
+        sources: I,
+        pred: P,
+    ) -> FxHashMap<Coordinates<T>, usize>
+    where
+        N: Neighborhood<T>,
+        I: IntoIterator<Item = Coordinates<T>>,
+        P: Fn((Coordinates<T>, &U), (Coordinates<T>, &U)) -> bool,
+    {
+        self.grid.distances_from(
+            &|pos| self.wrapped(neighborhood, pos),
+            sources.into_iter().map(|pos| self.wrap(pos)),
+            pred,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EightWay, FourWay, Offsets};
+
+    const MAZE: &str = r#"#####
+#S..#
+#.#E#
+#####"#;
+
+    #[test]
+    fn grid_parse_skips_unmapped_cells() {
+        let grid = Grid::<i16, char>::parse(MAZE, |c| (c != '#').then_some(c));
+
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid.get(&Coordinates::new(1, 1)), Some(&'S'));
+        assert_eq!(grid.get(&Coordinates::new(0, 0)), None);
+    }
+
+    #[test]
+    fn grid_searches_skip_missing_cells() {
+        let grid = Grid::<i16, char>::parse(MAZE, |c| (c != '#').then_some(c));
+        let start = *grid.find(&'S').unwrap();
+        let any = |_: (Coordinates<i16>, &char), _: (Coordinates<i16>, &char)| true;
+
+        assert_eq!(grid.bfs(&FourWay, start, |_, &c| c == 'E', any).len(), 4);
+        assert_eq!(
+            grid.bfs_all(&FourWay, start, |_, &c| c == 'E', any).len(),
+            1
+        );
+        assert_eq!(
+            grid.dijkstra(&FourWay, start, |_, &c| c == 'E', |_, _| Some(1)),
+            Some((3, grid.bfs(&FourWay, start, |_, &c| c == 'E', any)))
+        );
+        assert!(
+            grid.bfs(&FourWay, Coordinates::new(0, 0), |_, _| true, any)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn grid_parse_with_markers_finds_markers() {
+        let (grid, [start, end, missing]) =
+            Grid::<i16, bool>::parse_with_markers(MAZE, ['S', 'E', '^'], |c| Some(c == '#'));
+
+        assert_eq!(grid.len(), 20);
+        assert_eq!(start, Some(Coordinates::new(1, 1)));
+        assert_eq!(end, Some(Coordinates::new(3, 2)));
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn boolean_grid_parse_marks_matching_cells() {
+        let (grid, [start]) = BooleanGrid::<u8>::parse_with_markers(MAZE, ['S'], |c| c == '#');
+
+        assert_eq!(grid.len(), 15);
+        assert!(grid.contains(&Coordinates::new(2, 2)));
+        assert!(!grid.contains(&Coordinates::new(1, 1)));
+        assert_eq!(start, Some(Coordinates::new(1, 1)));
+    }
+
+    #[test]
+    fn grid_bounds_track_positive_coordinates() {
+        let mut grid = Grid::<i32, u8>::new();
+        grid.store(Coordinates::new(3, 7), 1);
+        grid.store(Coordinates::new(5, 4), 2);
+
+        assert_eq!(
+            grid.bounding_box(),
+            Some((Coordinates::new(3, 4), Coordinates::new(5, 7)))
+        );
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert!(!grid.within_bounds(Coordinates::new(0, 0)));
+    }
+
+    #[test]
+    fn grid_bounds_track_negative_coordinates() {
+        let mut grid = BooleanGrid::<i8>::new();
+        grid.mark(Coordinates::new(-2, -9));
+        grid.mark(Coordinates::new(-4, -1));
+
+        assert_eq!(
+            grid.bounding_box(),
+            Some((Coordinates::new(-4, -9), Coordinates::new(-2, -1)))
+        );
+        assert!(grid.within_bounds(Coordinates::new(-3, -5)));
+        assert!(!grid.within_bounds(Coordinates::new(0, -5)));
+    }
+
+    #[test]
+    fn grid_bounds_of_empty_grid() {
+        let grid = BooleanGrid::<u8>::new();
+
+        assert_eq!(grid.bounding_box(), None);
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
+        assert!(!grid.within_bounds(Coordinates::new(0, 0)));
+    }
+
+    #[test]
+    fn grid_recompute_bounds_shrinks_after_removal() {
+        let mut grid = Grid::<u16, char>::new();
+        grid.store(Coordinates::new(1, 1), 'a');
+        grid.store(Coordinates::new(8, 2), 'b');
+        grid.remove(&Coordinates::new(8, 2));
+
+        assert_eq!(grid.width(), 8);
+
+        grid.recompute_bounds();
+
+        assert_eq!(
+            grid.bounding_box(),
+            Some((Coordinates::new(1, 1), Coordinates::new(1, 1)))
+        );
+
+        grid.remove(&Coordinates::new(1, 1));
+        grid.recompute_bounds();
+
+        assert_eq!(grid.bounding_box(), None);
+    }
+
+    #[test]
+    fn grid_distances_from_several_sources() {
+        let grid = Grid::<i16, char>::parse(MAZE, |c| (c != '#').then_some(c));
+        let distances = grid.distances_from(
+            &FourWay,
+            [Coordinates::new(1, 1), Coordinates::new(3, 2)],
+            |_, _| true,
+        );
+
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&Coordinates::new(2, 1)], 1);
+        assert_eq!(distances[&Coordinates::new(3, 1)], 1);
+        assert_eq!(distances[&Coordinates::new(1, 2)], 1);
+        assert_eq!(distances.get(&Coordinates::new(2, 2)), None);
+    }
+
+    #[test]
+    fn grid_distances_to_follow_moves_backwards() {
+        let grid = Grid::<i8, u32>::parse("0123\n9994", |c| c.to_digit(10));
+        let uphill = |(_, a): (Coordinates<i8>, &u32), (_, b): (Coordinates<i8>, &u32)| b > a;
+
+        let from = grid.distances_from(&FourWay, [Coordinates::new(0, 0)], uphill);
+        let to = grid.distances_to(&FourWay, [Coordinates::new(3, 1)], uphill);
+
+        assert_eq!(from[&Coordinates::new(3, 1)], 4);
+        assert_eq!(to[&Coordinates::new(0, 0)], 4);
+        assert_eq!(to[&Coordinates::new(3, 0)], 1);
+        assert_eq!(to.get(&Coordinates::new(1, 1)), None);
+    }
+
+    #[test]
+    fn grid_distance_map_matches_sparse_distances() {
+        let grid = Grid::<i16, char>::parse(MAZE, |c| (c != '#').then_some(c));
+        let sparse = grid.distances_to(&FourWay, [Coordinates::new(3, 2)], |_, _| true);
+        let dense = grid.distance_map_to(&FourWay, [Coordinates::new(3, 2)], |_, _| true);
+
+        assert_eq!(dense.len(), sparse.len());
+        assert!(dense.iter().all(|(pos, d)| sparse[&pos] == d));
+        assert_eq!(dense.get(&Coordinates::new(0, 0)), None);
+        assert_eq!(dense.get(&Coordinates::new(-5, 40)), None);
+    }
+
+    #[test]
+    fn boolean_grid_distances_through_unmarked_cells() {
+        let walls = BooleanGrid::<i16>::parse(MAZE, |c| c == '#');
+        let sparse = walls.distances_from(&FourWay, [Coordinates::new(1, 1)], false);
+        let dense = walls.distance_map_from(&FourWay, [Coordinates::new(1, 1)], false);
+
+        assert_eq!(sparse[&Coordinates::new(3, 2)], 3);
+        assert_eq!(dense.get(&Coordinates::new(3, 2)), Some(3));
+        assert_eq!(dense.len(), 5);
+    }
+
+    #[test]
+    fn bounded_grid_parse_uses_text_extent() {
+        let grid = BooleanBoundedGrid::<i32>::parse(".#.\n...", |c| c == '#');
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.len(), 1);
+        assert!(grid.within_bounds(Coordinates::new(2, 1)));
+        assert!(!grid.within_bounds(Coordinates::new(3, 1)));
+    }
+
+    #[test]
+    fn grid_searches_with_eight_way_neighborhood() {
+        let (grid, [start, end]) = Grid::<i16, char>::parse_with_markers(MAZE, ['S', 'E'], Some);
+        let open = |_: (Coordinates<i16>, &char), (_, &c): (Coordinates<i16>, &char)| c != '#';
+
+        let path = grid.bfs(&EightWay, start.unwrap(), |pos, _| Some(pos) == end, open);
+        let distances = grid.distances_from(&EightWay, [start.unwrap()], open);
+
+        assert_eq!(path.len(), 3);
+        assert_eq!(distances[&end.unwrap()], 2);
+        assert_eq!(
+            grid.flood(&FourWay, Coordinates::new(2, 1), |a, b| a == b)
+                .len(),
+            2
+        );
+        assert_eq!(
+            grid.flood(&EightWay, Coordinates::new(2, 1), |a, b| a == b)
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn boolean_grid_bfs_with_custom_offsets() {
+        let walls = BooleanGrid::<i16>::parse(MAZE, |c| c == '#');
+        let jumps = Offsets::new(vec![Coordinates::new(2, 0), Coordinates::new(0, 1)]);
+        let path = walls.bfs(
+            &jumps,
+            Coordinates::new(1, 1),
+            Coordinates::new(3, 2),
+            false,
+        );
+
+        assert_eq!(
+            path,
+            vec![
+                Coordinates::new(1, 1),
+                Coordinates::new(3, 1),
+                Coordinates::new(3, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn coordinates_wrap_and_tile() {
+        let size = Coordinates::<i32>::new(11, 7);
+
+        assert_eq!(Coordinates::new(-1, 7).wrap(size), Coordinates::new(10, 0));
+        assert_eq!(Coordinates::new(-1, 7).tile(size), Coordinates::new(-1, 1));
+        assert_eq!(
+            Coordinates::new(23, -15).tile(size),
+            Coordinates::new(2, -3)
+        );
+        assert_eq!(
+            Coordinates::new(2, 4).wrapping_add(Coordinates::new(-10, 15), size),
+            Coordinates::new(3, 5)
+        );
+    }
+
+    #[test]
+    fn wrapping_grid_queries_any_tile() {
+        let grid = WrappingGrid::<i16, char>::parse("ab\ncd", Some);
+
+        assert_eq!(grid.size(), Coordinates::new(2, 2));
+        assert_eq!(grid.get(&Coordinates::new(-1, -1)), Some(&'d'));
+        assert_eq!(grid.get(&Coordinates::new(4, 3)), Some(&'c'));
+        assert_eq!(grid.tile(Coordinates::new(4, -3)), Coordinates::new(2, -2));
+    }
+
+    #[test]
+    fn wrapping_grid_searches_across_edges() {
+        let (grid, [start, end]) =
+            WrappingGrid::<i16, char>::parse_with_markers("S.#E", ['S', 'E'], Some);
+        let open = |_: (Coordinates<i16>, &char), (_, &c): (Coordinates<i16>, &char)| c != '#';
+
+        let path = grid.bfs(&FourWay, start.unwrap(), |pos, _| Some(pos) == end, open);
+        let distances = grid.distances_from(&FourWay, [Coordinates::new(-4, 0)], open);
+
+        assert_eq!(path, vec![Coordinates::new(0, 0), Coordinates::new(3, 0)]);
+        assert_eq!(distances[&Coordinates::new(1, 0)], 1);
+        assert_eq!(distances[&Coordinates::new(3, 0)], 1);
+        assert_eq!(distances.len(), 3);
+        assert_eq!(
+            grid.flood(&FourWay, Coordinates::new(1, 0), |_, &c| c != '#')
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn wrapping_grid_searches_across_unsigned_edges() {
+        let (grid, [start, end]) =
+            WrappingGrid::<u16, char>::parse_with_markers("S.#E", ['S', 'E'], Some);
+        let open = |_: (Coordinates<u16>, &char), (_, &c): (Coordinates<u16>, &char)| c != '#';
+
+        assert_eq!(
+            grid.bfs(&FourWay, start.unwrap(), |pos, _| Some(pos) == end, open),
+            vec![Coordinates::new(0, 0), Coordinates::new(3, 0)]
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use crate::{Coordinates, Neighborhood, ParseDirectionError};
+use num_traits::{PrimInt, Signed};
+use std::{
+    iter::{once, repeat_n},
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+/// A position on a hexagonal grid, in axial coordinates: `q` grows towards
+/// the east (or south-east), `r` towards the south. The third cube coordinate
+/// is `s = -q - r`.
+///
+/// Converts to and from [`Coordinates`] (`x = q`, `y = r`), so hex maps can be
+/// stored in a [`crate::Grid`] and searched with [`SixWay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Hex<T> {
+    q: T,
+    r: T,
+}
+
+impl<T> Hex<T> {
+    #[inline]
+    pub fn new(q: T, r: T) -> Self {
+        Self { q, r }
+    }
+}
+
+impl<T> Hex<T>
+where
+    T: Copy + Signed,
+{
+    /// Builds a position from cube coordinates, returning `None` unless
+    /// `q + r + s == 0`.
+    pub fn from_cube(q: T, r: T, s: T) -> Option<Self> {
+        (q + r + s).is_zero().then(|| Self::new(q, r))
+    }
+
+    #[inline]
+    pub fn q(&self) -> T {
+        self.q
+    }
+
+    #[inline]
+    pub fn r(&self) -> T {
+        self.r
+    }
+
+    #[inline]
+    pub fn s(&self) -> T {
+        -self.q - self.r
+    }
+
+    /// The neighbouring position towards `dir`.
+    pub fn next<D: HexDirection>(&self, dir: D) -> Self {
+        *self + dir.delta()
+    }
+
+    /// The six positions sharing an edge with this one.
+    pub fn neighbors(&self) -> [Hex<T>; 6] {
+        AXIAL.map(|(q, r)| *self + Self::new(unit(q), unit(r)))
+    }
+
+    /// Number of steps between two positions.
+    pub fn distance(&self, other: Self) -> T {
+        let d = *self - other;
+
+        (d.q.abs() + d.r.abs() + d.s().abs()) / (T::one() + T::one())
+    }
+}
+
+impl<T> Hex<T>
+where
+    T: PrimInt + Signed,
+{
+    /// Every position exactly `radius` steps away, walking around the ring.
+    /// A radius of zero yields the position itself.
+    pub fn ring(self, radius: T) -> impl Iterator<Item = Hex<T>> {
+        let steps = radius.abs().to_usize().unwrap();
+        let (q, r) = AXIAL[4];
+        let start = self + Self::new(unit::<T>(q) * radius.abs(), unit::<T>(r) * radius.abs());
+
+        let deltas = AXIAL
+            .into_iter()
+            .flat_map(move |(q, r)| repeat_n(Self::new(unit(q), unit(r)), steps));
+
+        once(start)
+            .chain(deltas.scan(start, |pos, delta| {
+                *pos = *pos + delta;
+                Some(*pos)
+            }))
+            .take((6 * steps).max(1))
+    }
+
+    /// Every position at most `radius` steps away, ring by ring from the
+    /// centre outwards.
+    pub fn within(self, radius: T) -> impl Iterator<Item = Hex<T>> {
+        (0..=radius.abs().to_usize().unwrap())
+            .flat_map(move |radius| self.ring(T::from(radius).unwrap()))
+    }
+}
+
+/// Axial offsets of the six neighbours, going counterclockwise from east.
+const AXIAL: [(i8, i8); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+fn unit<T: Signed>(v: i8) -> T {
+    match v {
+        1 => T::one(),
+        -1 => -T::one(),
+        _ => T::zero(),
+    }
+}
+
+impl<T> From<Hex<T>> for Coordinates<T> {
+    fn from(value: Hex<T>) -> Self {
+        Coordinates::new(value.q, value.r)
+    }
+}
+
+impl<T: Copy> From<Coordinates<T>> for Hex<T> {
+    fn from(value: Coordinates<T>) -> Self {
+        Hex::new(value.x(), value.y())
+    }
+}
+
+impl<T: Add<Output = T>> Add for Hex<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            q: self.q + rhs.q,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Hex<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            q: self.q - rhs.q,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+/// The six edges of a hexagon, as [`Hex`] offsets.
+///
+/// Implemented by [`FlatHexDirection`], for hexagons with a flat top, and
+/// [`PointyHexDirection`], for hexagons with a pointy top.
+pub trait HexDirection: Copy + FromStr<Err = ParseDirectionError> {
+    fn delta<T: Signed>(&self) -> Hex<T>;
+
+    /// Parses a path of directions, written either separated by commas or
+    /// whitespace (`ne,ne,s`) or run together (`esenee`).
+    fn parse_path(s: &str) -> Result<Vec<Self>, ParseDirectionError> {
+        let mut path = vec![];
+        let mut rest = s.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+
+        while !rest.is_empty() {
+            let (dir, len) = rest
+                .get(..2)
+                .and_then(|two| two.parse().ok().map(|dir| (dir, 2)))
+                .map_or_else(
+                    || {
+                        let len = rest.chars().next().map_or(0, char::len_utf8);
+                        Ok((rest[..len].parse()?, len))
+                    },
+                    Ok,
+                )?;
+
+            path.push(dir);
+            rest = rest[len..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+
+        Ok(path)
+    }
+}
+
+/// Directions on a grid of flat-topped hexagons: `n`, `ne`, `se`, `s`, `sw`
+/// and `nw`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Hash)]
+pub enum FlatHexDirection {
+    #[default]
+    North,
+    Northeast,
+    Southeast,
+    South,
+    Southwest,
+    Northwest,
+}
+
+/// Directions on a grid of pointy-topped hexagons: `e`, `se`, `sw`, `w`, `nw`
+/// and `ne`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Hash)]
+pub enum PointyHexDirection {
+    #[default]
+    East,
+    Southeast,
+    Southwest,
+    West,
+    Northwest,
+    Northeast,
+}
+
+impl FlatHexDirection {
+    /// Every direction, clockwise from [`FlatHexDirection::North`].
+    pub const ALL: [FlatHexDirection; 6] = [
+        Self::North,
+        Self::Northeast,
+        Self::Southeast,
+        Self::South,
+        Self::Southwest,
+        Self::Northwest,
+    ];
+
+    #[inline]
+    #[must_use]
+    pub fn clockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 6]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn counterclockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 5) % 6]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 6]
+    }
+}
+
+impl PointyHexDirection {
+    /// Every direction, clockwise from [`PointyHexDirection::East`].
+    pub const ALL: [PointyHexDirection; 6] = [
+        Self::East,
+        Self::Southeast,
+        Self::Southwest,
+        Self::West,
+        Self::Northwest,
+        Self::Northeast,
+    ];
+
+    #[inline]
+    #[must_use]
+    pub fn clockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 6]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn counterclockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 5) % 6]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 6]
+    }
+}
+
+impl HexDirection for FlatHexDirection {
+    fn delta<T: Signed>(&self) -> Hex<T> {
+        use FlatHexDirection::*;
+
+        let (q, r) = match self {
+            North => (0, -1),
+            Northeast => (1, -1),
+            Southeast => (1, 0),
+            South => (0, 1),
+            Southwest => (-1, 1),
+            Northwest => (-1, 0),
+        };
+
+        Hex::new(unit(q), unit(r))
+    }
+}
+
+impl HexDirection for PointyHexDirection {
+    fn delta<T: Signed>(&self) -> Hex<T> {
+        use PointyHexDirection::*;
+
+        let (q, r) = match self {
+            East => (1, 0),
+            Southeast => (0, 1),
+            Southwest => (-1, 1),
+            West => (-1, 0),
+            Northwest => (0, -1),
+            Northeast => (1, -1),
+        };
+
+        Hex::new(unit(q), unit(r))
+    }
+}
+
+impl FromStr for FlatHexDirection {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use FlatHexDirection::*;
+
+        match s.to_ascii_lowercase().as_str() {
+            "n" => Ok(North),
+            "ne" => Ok(Northeast),
+            "se" => Ok(Southeast),
+            "s" => Ok(South),
+            "sw" => Ok(Southwest),
+            "nw" => Ok(Northwest),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for PointyHexDirection {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use PointyHexDirection::*;
+
+        match s.to_ascii_lowercase().as_str() {
+            "e" => Ok(East),
+            "se" => Ok(Southeast),
+            "sw" => Ok(Southwest),
+            "w" => Ok(West),
+            "nw" => Ok(Northwest),
+            "ne" => Ok(Northeast),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+/// The six neighbours of a hex position stored as [`Coordinates`], as returned
+/// by [`Hex::neighbors`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SixWay;
+
+impl<T> Neighborhood<T> for SixWay
+where
+    T: Copy + Signed,
+{
+    #[inline]
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>> {
+        Hex::from(pos)
+            .neighbors()
+            .into_iter()
+            .map(Coordinates::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Grid;
+    use rustc_hash::FxHashSet;
+
+    #[test]
+    fn parses_paths_in_both_styles() {
+        let flat = FlatHexDirection::parse_path("ne,ne,s,s").unwrap();
+        let pointy = PointyHexDirection::parse_path("esenee").unwrap();
+
+        assert_eq!(
+            flat.iter()
+                .fold(Hex::<i32>::default(), |pos, &dir| pos.next(dir)),
+            Hex::new(2, 0)
+        );
+        assert_eq!(
+            pointy,
+            [
+                PointyHexDirection::East,
+                PointyHexDirection::Southeast,
+                PointyHexDirection::Northeast,
+                PointyHexDirection::East
+            ]
+        );
+        assert!(FlatHexDirection::parse_path("n,e").is_err());
+    }
+
+    #[test]
+    fn directions_turn_and_reverse() {
+        for dir in FlatHexDirection::ALL {
+            assert_eq!(dir.clockwise().counterclockwise(), dir);
+            assert_eq!(dir.opposite().delta::<i8>(), Hex::default() - dir.delta());
+        }
+        for dir in PointyHexDirection::ALL {
+            assert_eq!(dir.opposite().delta::<i8>(), Hex::default() - dir.delta());
+        }
+    }
+
+    #[test]
+    fn distances_and_rings() {
+        let center = Hex::new(3i32, -1);
+
+        assert_eq!(Hex::<i32>::default().distance(Hex::new(3, -1)), 3);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), vec![center]);
+
+        for radius in 1..5 {
+            let ring = center.ring(radius).collect::<FxHashSet<_>>();
+
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|pos| pos.distance(center) == radius));
+        }
+        assert_eq!(center.within(3).count(), 37);
+        assert!(
+            center
+                .neighbors()
+                .iter()
+                .all(|pos| pos.distance(center) == 1)
+        );
+    }
+
+    #[test]
+    fn grid_searches_through_six_way() {
+        let mut grid = Grid::<i32, bool>::new();
+        Coordinates::new(-3, -3)
+            .range_inclusive(Coordinates::new(3, 3))
+            .for_each(|pos| {
+                grid.store(pos, true);
+            });
+
+        let target = Coordinates::from(Hex::new(3, -3));
+        let path = grid.bfs(
+            &SixWay,
+            Coordinates::new(0, 0),
+            |pos, _| pos == target,
+            |_, _| true,
+        );
+
+        assert_eq!(path.len(), 4);
+    }
+}
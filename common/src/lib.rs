@@ -1,13 +1,50 @@
 mod answer;
+mod automaton;
+mod bit_grid;
+mod bit_matrix;
+mod compression;
 mod coordinates;
+mod coordinates3;
+mod dense_grid;
 mod digit_string;
 mod direction;
+mod distance_map;
 pub mod distances;
+mod geometry;
 mod grid;
+mod hex;
+pub mod math;
+mod matrix;
+mod neighborhood;
+mod rational;
+mod ray;
+mod rectangle;
+mod regions;
+mod render;
+pub mod search;
+pub mod sieve;
+mod transform;
 pub mod utils;
 
 pub use answer::Answer;
+pub use automaton::{Automaton, BirthSurvival, Rule};
+pub use bit_grid::BitGrid;
+pub use bit_matrix::{BitMatrix, BitSolution};
+pub use compression::{AxisCompression, Compression};
 pub use coordinates::Coordinates;
+pub use coordinates3::Coordinates3;
+pub use dense_grid::{DenseGrid, PrefixSums};
 pub use digit_string::{DigitString, DigitStringU128};
-pub use direction::Direction;
-pub use grid::{BooleanBoundedGrid, BooleanGrid, BoundedGrid, Grid};
+pub use direction::{Direction, Direction8, ParseDirectionError};
+pub use distance_map::DistanceMap;
+pub use geometry::{Location, Polygon, Segment};
+pub use grid::{BooleanBoundedGrid, BooleanGrid, BoundedGrid, Grid, WrappingGrid};
+pub use hex::{FlatHexDirection, Hex, HexDirection, PointyHexDirection, SixWay};
+pub use matrix::{Matrix, Solution};
+pub use neighborhood::{EightWay, FourWay, Neighborhood, Offsets, Wrapping};
+pub use rational::Rational;
+pub use ray::Step;
+pub use rectangle::{Rectangle, RectangleSet};
+pub use regions::{Region, Regions};
+pub use render::Render;
+pub use transform::{BooleanGridWindow, GridWindow, Transform};
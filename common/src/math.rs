@@ -0,0 +1,287 @@
+use num_traits::{PrimInt, Signed};
+
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Least common multiple, zero if either value is zero.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    if a.is_zero() || b.is_zero() {
+        T::zero()
+    } else {
+        abs(a) / gcd(a, b) * abs(b)
+    }
+}
+
+/// Greatest common divisor of every value, zero for no values.
+pub fn gcd_all<T: PrimInt, I: IntoIterator<Item = T>>(values: I) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+/// Least common multiple of every value, one for no values.
+pub fn lcm_all<T: PrimInt, I: IntoIterator<Item = T>>(values: I) -> T {
+    values.into_iter().fold(T::one(), lcm)
+}
+
+/// Extended Euclid: `(g, x, y)` such that `a * x + b * y == g`, where `g` is
+/// the non-negative greatest common divisor of `a` and `b`.
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a` brought into `0..modulus`, even when negative.
+fn normalize<T: PrimInt>(a: T, modulus: T) -> T {
+    let r = a % modulus;
+
+    if r < T::zero() { r + modulus } else { r }
+}
+
+/// `(a + b) % modulus` for `a` and `b` in `0..modulus`, without overflowing.
+fn add_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a - b) % modulus` for `a` and `b` in `0..modulus`, never negative.
+fn sub_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if a >= b { a - b } else { modulus - (b - a) }
+}
+
+/// `a * b` modulo a positive `modulus`, in `0..modulus`, without
+/// overflowing even when the product doesn't fit in `T`.
+pub fn mul_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    let (mut a, mut b) = (normalize(a, modulus), normalize(b, modulus));
+
+    if let Some(product) = a.checked_mul(&b) {
+        return product % modulus;
+    }
+
+    let mut result = T::zero();
+    while !b.is_zero() {
+        if b & T::one() == T::one() {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b >> 1;
+    }
+
+    result
+}
+
+/// `base` to the power of a non-negative `exp` modulo a positive `modulus`,
+/// in `0..modulus`.
+pub fn pow_mod<T: PrimInt>(base: T, mut exp: T, modulus: T) -> T {
+    let mut base = normalize(base, modulus);
+    let mut result = normalize(T::one(), modulus);
+
+    while exp > T::zero() {
+        if exp & T::one() == T::one() {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp = exp >> 1;
+    }
+
+    result
+}
+
+/// The `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, or `None`
+/// if `a` and `modulus` aren't coprime.
+pub fn mod_inverse<T: PrimInt>(a: T, modulus: T) -> Option<T> {
+    if modulus == T::one() {
+        return Some(T::zero());
+    }
+
+    // Extended Euclid, keeping the coefficient of `a` modulo `modulus` so it
+    // never goes negative.
+    let (mut old_r, mut r) = (normalize(a, modulus), modulus);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, sub_mod(old_x, mul_mod(q, x, modulus), modulus));
+    }
+
+    (old_r == T::one()).then_some(old_x)
+}
+
+/// Chinese remainder theorem over `(remainder, modulus)` congruences whose
+/// moduli don't need to be coprime. Returns the smallest non-negative
+/// solution along with the modulus it repeats with, or `None` if the
+/// congruences contradict each other or the combined modulus overflows.
+pub fn crt<T: PrimInt, I: IntoIterator<Item = (T, T)>>(congruences: I) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::zero(), T::one()), |(r1, m1), (r2, m2)| {
+            let r2 = normalize(r2, m2);
+            let g = gcd(m1, m2);
+            let diff = sub_mod(r2, normalize(r1, m2), m2);
+
+            if !(diff % g).is_zero() {
+                return None;
+            }
+
+            let m2g = m2 / g;
+            let k = mul_mod(diff / g, mod_inverse(m1 / g, m2g)?, m2g);
+            let modulus = m1.checked_mul(&m2g)?;
+
+            Some((r1 + m1 * k, modulus))
+        })
+}
+
+/// Largest integer whose square is at most `n`.
+///
+/// # Panics
+///
+/// If `n` is negative.
+pub fn isqrt<T: PrimInt>(n: T) -> T {
+    assert!(n >= T::zero(), "square root of a negative number");
+
+    if n < T::one() + T::one() {
+        return n;
+    }
+
+    // Newton's method, starting above the root without overflowing.
+    let two = T::one() + T::one();
+    let mut x = n / two + T::one();
+    loop {
+        let y = (x + n / x) / two;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+fn abs<T: PrimInt>(a: T) -> T {
+    if a < T::zero() { T::zero() - a } else { a }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        for a in -30i32..=30 {
+            for b in -30i32..=30 {
+                let expected = (1..=30)
+                    .rev()
+                    .find(|d| a % d == 0 && b % d == 0)
+                    .filter(|_| a != 0 || b != 0)
+                    .unwrap_or(0);
+
+                assert_eq!(gcd(a, b), expected);
+                if a != 0 && b != 0 {
+                    assert_eq!(lcm(a, b), (a * b).abs() / expected);
+                }
+            }
+        }
+
+        assert_eq!(gcd_all([12u64, 18, 30]), 6);
+        assert_eq!(gcd_all(Vec::<u8>::new()), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u8>::new()), 1);
+        assert_eq!(lcm(0u8, 5), 0);
+    }
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        for a in -50i16..=50 {
+            for b in -50i16..=50 {
+                let (g, x, y) = extended_gcd(a, b);
+
+                assert_eq!(g, gcd(a, b));
+                assert_eq!(a * x + b * y, g);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_mod_and_pow_mod_never_overflow() {
+        for m in 1u8..=255 {
+            for a in (0u8..=255).step_by(7) {
+                for b in (0u8..=255).step_by(5) {
+                    assert_eq!(mul_mod(a, b, m) as u32, a as u32 * b as u32 % m as u32);
+                }
+
+                let expected = (0..13).fold(1 % m as u32, |acc, _| acc * a as u32 % m as u32);
+                assert_eq!(pow_mod(a, 13, m) as u32, expected);
+            }
+        }
+
+        assert_eq!(mul_mod(-7i32, 3, 5), 4);
+        assert_eq!(pow_mod(252_533u64, 1_000_000_006, 1_000_000_007), 1);
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 2, u64::MAX), 2);
+    }
+
+    #[test]
+    fn mod_inverse_matches_brute_force() {
+        for m in 1u8..=100 {
+            for a in 0..m {
+                let expected = (0..m).find(|&x| (a as u32 * x as u32) % m as u32 == 1 % m as u32);
+
+                assert_eq!(mod_inverse(a, m), expected);
+            }
+        }
+
+        assert_eq!(mod_inverse(-3i64, 7), Some(2));
+    }
+
+    #[test]
+    fn crt_handles_non_coprime_moduli() {
+        for m1 in 1u32..=12 {
+            for m2 in 1u32..=12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let l = lcm(m1, m2);
+                        let expected = (0..l).find(|x| x % m1 == r1 && x % m2 == r2);
+
+                        assert_eq!(crt([(r1, m1), (r2, m2)]), expected.map(|x| (x, l)));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(Vec::<(u8, u8)>::new()), Some((0, 1)));
+        assert_eq!(crt([(1u8, 200), (2, 199)]), None);
+    }
+
+    #[test]
+    fn isqrt_is_floor_of_root() {
+        for n in 0u16..=u16::MAX {
+            let root = isqrt(n) as u32;
+
+            assert!(root * root <= n as u32 && (root + 1) * (root + 1) > n as u32);
+        }
+
+        assert_eq!(isqrt(i8::MAX), 11);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+}
@@ -0,0 +1,343 @@
+use crate::Rational;
+use num_traits::{PrimInt, Signed};
+use std::ops::{Index, IndexMut};
+
+/// A dense matrix stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    rows: usize,
+    columns: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Matrix<T> {
+    #[must_use]
+    pub fn new(rows: usize, columns: usize, fill: T) -> Self {
+        Self {
+            rows,
+            columns,
+            cells: vec![fill; rows * columns],
+        }
+    }
+
+    /// Builds a matrix from its rows.
+    ///
+    /// # Panics
+    ///
+    /// If the rows don't all have the same length.
+    #[must_use]
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let columns = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == columns),
+            "rows of different lengths"
+        );
+
+        Self {
+            rows: rows.len(),
+            columns,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// The matrix with `column` added as a last column.
+    #[must_use]
+    pub fn augmented(&self, column: &[T]) -> Self {
+        assert_eq!(column.len(), self.rows, "column of the wrong length");
+
+        Self::from_rows(
+            (0..self.rows)
+                .map(|r| {
+                    let mut row = self.row(r).to_vec();
+                    row.push(column[r].clone());
+                    row
+                })
+                .collect(),
+        )
+    }
+
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Matrix<U> {
+        Matrix {
+            rows: self.rows,
+            columns: self.columns,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Matrix<T> {
+    #[must_use]
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        (row < self.rows && column < self.columns).then(|| &self.cells[row * self.columns + column])
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for c in 0..self.columns {
+                self.cells.swap(a * self.columns + c, b * self.columns + c);
+            }
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.cells[row * self.columns + column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self.cells[row * self.columns + column]
+    }
+}
+
+impl<T: PrimInt + Signed> Matrix<T> {
+    #[must_use]
+    pub fn to_rational(&self) -> Matrix<Rational<T>> {
+        self.map(|&v| Rational::from(v))
+    }
+
+    /// Number of linearly independent rows, computed exactly over the
+    /// rationals.
+    #[must_use]
+    pub fn rank_exact(&self) -> usize {
+        self.to_rational().rank()
+    }
+
+    /// Every solution of `self * x = rhs` over the rationals, or `None` if
+    /// there is none. The solutions may still be fractional, it's up to the
+    /// caller to keep the integer ones.
+    #[must_use]
+    pub fn solve_exact(&self, rhs: &[T]) -> Option<Solution<T>> {
+        let rhs = rhs.iter().map(|&v| Rational::from(v)).collect::<Vec<_>>();
+
+        self.to_rational().solve(&rhs)
+    }
+}
+
+impl<T: PrimInt + Signed> Matrix<Rational<T>> {
+    /// Brings the matrix into reduced row echelon form with Gauss-Jordan
+    /// elimination, returning the column of each pivot, row by row.
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = vec![];
+
+        for column in 0..self.columns {
+            let row = pivots.len();
+            let Some(pivot) = (row..self.rows).find(|&r| !self[(r, column)].is_zero()) else {
+                continue;
+            };
+
+            self.swap_rows(row, pivot);
+
+            let scale = self[(row, column)].recip();
+            for c in column..self.columns {
+                self[(row, c)] = self[(row, c)] * scale;
+            }
+
+            for r in (0..self.rows).filter(|&r| r != row) {
+                let factor = self[(r, column)];
+                if !factor.is_zero() {
+                    for c in column..self.columns {
+                        let delta = factor * self[(row, c)];
+                        self[(r, c)] -= delta;
+                    }
+                }
+            }
+
+            pivots.push(column);
+            if pivots.len() == self.rows {
+                break;
+            }
+        }
+
+        pivots
+    }
+
+    /// Number of linearly independent rows.
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    /// Every solution of `self * x = rhs`, or `None` if there is none.
+    #[must_use]
+    pub fn solve(&self, rhs: &[Rational<T>]) -> Option<Solution<T>> {
+        let mut reduced = self.augmented(rhs);
+        let pivots = reduced.row_reduce();
+
+        if pivots.last() == Some(&self.columns) {
+            return None;
+        }
+
+        let mut particular = vec![Rational::zero(); self.columns];
+        pivots
+            .iter()
+            .enumerate()
+            .for_each(|(row, &column)| particular[column] = reduced[(row, self.columns)]);
+
+        let free = (0..self.columns)
+            .filter(|c| !pivots.contains(c))
+            .collect::<Vec<_>>();
+
+        let nullspace = free
+            .iter()
+            .map(|&f| {
+                let mut basis = vec![Rational::zero(); self.columns];
+                basis[f] = Rational::one();
+                pivots
+                    .iter()
+                    .enumerate()
+                    .for_each(|(row, &column)| basis[column] = -reduced[(row, f)]);
+
+                basis
+            })
+            .collect();
+
+        Some(Solution {
+            particular,
+            nullspace,
+            free,
+        })
+    }
+
+    /// Basis of the vectors `x` such that `self * x = 0`.
+    #[must_use]
+    pub fn nullspace(&self) -> Vec<Vec<Rational<T>>> {
+        self.solve(&vec![Rational::zero(); self.rows])
+            .map(|solution| solution.nullspace)
+            .unwrap_or_default()
+    }
+}
+
+/// The solutions of a linear system: the particular solution plus any
+/// combination of the nullspace basis vectors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<T> {
+    particular: Vec<Rational<T>>,
+    nullspace: Vec<Vec<Rational<T>>>,
+    free: Vec<usize>,
+}
+
+impl<T: PrimInt + Signed> Solution<T> {
+    /// The solution where every free variable is zero.
+    #[must_use]
+    #[inline]
+    pub fn particular(&self) -> &[Rational<T>] {
+        &self.particular
+    }
+
+    /// One basis vector per free variable, in the order of
+    /// [`Self::free_variables`].
+    #[must_use]
+    #[inline]
+    pub fn nullspace(&self) -> &[Vec<Rational<T>>] {
+        &self.nullspace
+    }
+
+    /// Indices of the variables that can take any value.
+    #[must_use]
+    #[inline]
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_unique(&self) -> bool {
+        self.nullspace.is_empty()
+    }
+
+    /// The solution where the free variables take the given values.
+    #[must_use]
+    pub fn with_free(&self, values: &[T]) -> Vec<Rational<T>> {
+        let mut x = self.particular.clone();
+
+        for (basis, &value) in self.nullspace.iter().zip(values) {
+            let value = Rational::from(value);
+            x.iter_mut().zip(basis).for_each(|(x, &b)| *x += b * value);
+        }
+
+        x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matrix(rows: &[&[i64]]) -> Matrix<i64> {
+        Matrix::from_rows(rows.iter().map(|row| row.to_vec()).collect())
+    }
+
+    fn times(m: &Matrix<i64>, x: &[Rational<i64>]) -> Vec<Rational<i64>> {
+        (0..m.rows())
+            .map(|r| {
+                m.row(r)
+                    .iter()
+                    .zip(x)
+                    .fold(Rational::zero(), |sum, (&a, &x)| {
+                        sum + Rational::from(a) * x
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unique_solution() {
+        let m = matrix(&[&[94, 22], &[34, 67]]);
+        let solution = m.solve_exact(&[8400, 5400]).unwrap();
+
+        assert!(solution.is_unique());
+        assert_eq!(
+            solution.particular(),
+            &[Rational::from(80), Rational::from(40)]
+        );
+        assert_eq!(m.rank_exact(), 2);
+    }
+
+    #[test]
+    fn underdetermined_solution_space() {
+        let m = matrix(&[&[1, 2, 3, 4], &[2, 4, 7, 9], &[1, 2, 4, 5]]);
+        let rhs = [10, 22, 12];
+        let solution = m.solve_exact(&rhs).unwrap();
+        let rhs = rhs.map(Rational::from).to_vec();
+
+        assert_eq!(m.rank_exact(), 2);
+        assert_eq!(solution.free_variables(), &[1, 3]);
+        assert_eq!(times(&m, solution.particular()), rhs);
+        assert_eq!(times(&m, &solution.with_free(&[3, -2])), rhs);
+        for basis in solution.nullspace() {
+            assert!(times(&m, basis).iter().all(Rational::is_zero));
+        }
+        assert_eq!(m.to_rational().nullspace().len(), 2);
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let m = matrix(&[&[1, 1], &[2, 2]]);
+
+        assert_eq!(m.solve_exact(&[1, 3]), None);
+        assert!(m.solve_exact(&[1, 2]).is_some());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{Coordinates, distances::within_manhattan};
+use num_traits::{Euclid, PrimInt, Signed};
+use std::ops::Add;
+
+/// The positions a grid algorithm may move to from a given cell.
+///
+/// Implemented by [`FourWay`], [`EightWay`], [`Offsets`], [`Wrapping`] and any
+/// closure mapping a position to an iterable of positions.
+pub trait Neighborhood<T> {
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>>;
+}
+
+/// Up, right, down and left, as returned by [`Coordinates::checked_neighbors`],
+/// so unsigned positions don't underflow at zero. Positions that don't fit in
+/// `T` are skipped rather than wrapped, [`Wrapping`] and
+/// [`crate::WrappingGrid`] looking around a copy of the tile away from zero
+/// to still reach across its edges.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FourWay;
+
+/// The four orthogonal and four diagonal neighbours, as returned by
+/// [`Coordinates::checked_neighbors_8`], skipping positions that don't fit in
+/// `T` like [`FourWay`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EightWay;
+
+impl<T: PrimInt> Neighborhood<T> for FourWay {
+    #[inline]
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>> {
+        pos.checked_neighbors()
+    }
+}
+
+impl<T: PrimInt> Neighborhood<T> for EightWay {
+    #[inline]
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>> {
+        pos.checked_neighbors_8()
+    }
+}
+
+/// A fixed set of offsets added to the current position.
+#[derive(Debug, Clone)]
+pub struct Offsets<T> {
+    offsets: Vec<Coordinates<T>>,
+}
+
+impl<T> Offsets<T> {
+    #[must_use]
+    pub fn new(offsets: Vec<Coordinates<T>>) -> Self {
+        Self { offsets }
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Coordinates<T>> {
+        self.offsets.iter()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+impl<T> Offsets<T>
+where
+    T: PrimInt + Signed,
+{
+    /// The eight moves of a chess knight.
+    #[must_use]
+    pub fn knight() -> Self {
+        let (one, two) = (T::one(), T::one() + T::one());
+
+        Self::new(
+            [
+                (one, -two),
+                (two, -one),
+                (two, one),
+                (one, two),
+                (-one, two),
+                (-two, one),
+                (-two, -one),
+                (-one, -two),
+            ]
+            .into_iter()
+            .map(|(x, y)| Coordinates::new(x, y))
+            .collect(),
+        )
+    }
+
+    /// Every position at a manhattan distance of at most `radius`, excluding
+    /// the position itself.
+    #[must_use]
+    pub fn manhattan(radius: T) -> Self {
+        Self::new(
+            within_manhattan(Coordinates::new(T::zero(), T::zero()), radius)
+                .filter(|offset| !offset.x().is_zero() || !offset.y().is_zero())
+                .collect(),
+        )
+    }
+}
+
+impl<T> Neighborhood<T> for Offsets<T>
+where
+    T: Copy + Add<Output = T>,
+{
+    #[inline]
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>> {
+        self.offsets.iter().map(move |&offset| pos + offset)
+    }
+}
+
+/// Another neighbourhood whose positions wrap around an area of the given
+/// size starting at `(0, 0)`, turning the searches of a grid covering that
+/// area into searches on a torus.
+#[derive(Debug, Clone, Copy)]
+pub struct Wrapping<N, T> {
+    neighborhood: N,
+    size: Coordinates<T>,
+}
+
+impl<N, T> Wrapping<N, T> {
+    #[must_use]
+    pub fn new(neighborhood: N, size: Coordinates<T>) -> Self {
+        Self { neighborhood, size }
+    }
+}
+
+impl<N, T> Neighborhood<T> for Wrapping<N, T>
+where
+    N: Neighborhood<T>,
+    T: Copy + Euclid + PrimInt,
+{
+    #[inline]
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>> {
+        // Looking around the copy of `pos` one tile further right and down,
+        // stepping back from `0` gives `size - 1` instead of underflowing
+        // unsigned coordinates.
+        self.neighborhood
+            .neighbors(pos.wrap(self.size) + self.size)
+            .map(|neighbor| neighbor.wrap(self.size))
+    }
+}
+
+impl<T, F, I> Neighborhood<T> for F
+where
+    F: Fn(Coordinates<T>) -> I,
+    I: IntoIterator<Item = Coordinates<T>>,
+{
+    #[inline]
+    fn neighbors(&self, pos: Coordinates<T>) -> impl Iterator<Item = Coordinates<T>> {
+        self(pos).into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rustc_hash::FxHashSet;
+
+    #[test]
+    fn eight_way_includes_diagonals() {
+        let pos = Coordinates::<i8>::new(0, 0);
+
+        assert_eq!(FourWay.neighbors(pos).count(), 4);
+        assert_eq!(EightWay.neighbors(pos).count(), 8);
+        assert!(EightWay.neighbors(pos).any(|n| n == Coordinates::new(1, 1)));
+    }
+
+    #[test]
+    fn knight_moves() {
+        let moves = Offsets::<i8>::knight()
+            .neighbors(Coordinates::new(4, 4))
+            .collect::<Vec<_>>();
+
+        assert_eq!(moves.len(), 8);
+        assert!(moves.contains(&Coordinates::new(5, 2)));
+        assert!(moves.contains(&Coordinates::new(2, 3)));
+    }
+
+    #[test]
+    fn manhattan_disk() {
+        assert_eq!(Offsets::<i16>::manhattan(2).len(), 12);
+        assert_eq!(Offsets::<i16>::manhattan(20).len(), 840);
+        assert!(
+            Offsets::<i16>::manhattan(20)
+                .iter()
+                .all(|o| o.x().abs() + o.y().abs() <= 20 && *o != Coordinates::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn wrapping_neighborhood_stays_inside_area() {
+        let wrapping = Wrapping::new(EightWay, Coordinates::<i8>::new(3, 2));
+        let neighbors = wrapping
+            .neighbors(Coordinates::new(0, 0))
+            .collect::<Vec<_>>();
+
+        assert!(neighbors.contains(&Coordinates::new(2, 1)));
+        assert!(
+            neighbors
+                .iter()
+                .all(|n| (0..3).contains(&n.x()) && (0..2).contains(&n.y()))
+        );
+    }
+
+    #[test]
+    fn checked_neighborhoods_skip_unsigned_underflow_unless_wrapped() {
+        let origin = Coordinates::<u8>::new(0, 0);
+        let wrapping = Wrapping::new(EightWay, Coordinates::new(4, 4));
+
+        assert_eq!(FourWay.neighbors(origin).count(), 2);
+        assert_eq!(EightWay.neighbors(origin).count(), 3);
+        assert_eq!(
+            wrapping.neighbors(origin).collect::<FxHashSet<_>>(),
+            Wrapping::new(EightWay, Coordinates::<i8>::new(4, 4))
+                .neighbors(Coordinates::new(0, 0))
+                .map(|n| Coordinates::new(n.x() as u8, n.y() as u8))
+                .collect()
+        );
+    }
+
+    #[test]
+    fn wrapping_neighborhood_wraps_unsigned_zero() {
+        let wrapping = Wrapping::new(FourWay, Coordinates::<u8>::new(4, 3));
+        let neighbors = wrapping
+            .neighbors(Coordinates::new(0, 0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            neighbors,
+            vec![
+                Coordinates::new(0, 2),
+                Coordinates::new(1, 0),
+                Coordinates::new(0, 1),
+                Coordinates::new(3, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn closures_are_neighborhoods() {
+        let right = |pos: Coordinates<i8>| [pos.right()];
+
+        assert_eq!(
+            right.neighbors(Coordinates::new(0, 0)).collect::<Vec<_>>(),
+            vec![Coordinates::new(1, 0)]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::math::gcd;
+use num_traits::{PrimInt, Signed};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+/// An exact fraction, always kept reduced with a positive denominator so
+/// equal values compare and hash equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: PrimInt + Signed> Rational<T> {
+    /// `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// If `denominator` is zero.
+    #[must_use]
+    pub fn new(numerator: T, denominator: T) -> Self {
+        assert!(!denominator.is_zero(), "zero denominator");
+
+        let g = gcd(numerator, denominator) * denominator.signum();
+
+        Self {
+            numerator: numerator / g,
+            denominator: denominator / g,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn zero() -> Self {
+        Self::from(T::zero())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn one() -> Self {
+        Self::from(T::one())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn numerator(&self) -> T {
+        self.numerator
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn denominator(&self) -> T {
+        self.denominator
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.denominator.is_one()
+    }
+
+    /// The value as an integer, if it is one.
+    #[must_use]
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    /// Largest integer not greater than the value.
+    #[must_use]
+    pub fn floor(&self) -> T {
+        let q = self.numerator / self.denominator;
+
+        if self.numerator % self.denominator < T::zero() {
+            q - T::one()
+        } else {
+            q
+        }
+    }
+
+    /// Smallest integer not less than the value.
+    #[must_use]
+    pub fn ceil(&self) -> T {
+        -(-*self).floor()
+    }
+
+    /// `1 / self`.
+    ///
+    /// # Panics
+    ///
+    /// If the value is zero.
+    #[must_use]
+    pub fn recip(&self) -> Self {
+        Self::new(self.denominator, self.numerator)
+    }
+}
+
+impl<T: PrimInt + Signed> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self {
+            numerator: value,
+            denominator: T::one(),
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.denominator, rhs.denominator);
+        let (a, b) = (self.denominator / g, rhs.denominator / g);
+
+        Self::new(self.numerator * b + rhs.numerator * a, self.denominator * b)
+    }
+}
+
+impl<T: PrimInt + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: PrimInt + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Cross-reduce first to keep the products small.
+        let g1 = gcd(self.numerator, rhs.denominator);
+        let g2 = gcd(rhs.numerator, self.denominator);
+
+        Self::new(
+            (self.numerator / g1) * (rhs.numerator / g2),
+            (self.denominator / g2) * (rhs.denominator / g1),
+        )
+    }
+}
+
+impl<T: PrimInt + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.recip()
+    }
+}
+
+impl<T: PrimInt + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> AddAssign for Rational<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: PrimInt + Signed> SubAssign for Rational<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: PrimInt + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PrimInt + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl<T: Display + PrimInt> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic_stays_reduced() {
+        let half = Rational::new(3i64, -6);
+        let third = Rational::new(1, 3);
+
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half + third, Rational::new(-1, 6));
+        assert_eq!(half - third, Rational::new(-5, 6));
+        assert_eq!(half * third, Rational::new(-1, 6));
+        assert_eq!(half / third, Rational::new(-3, 2));
+        assert_eq!(Rational::new(4, 2).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(Rational::zero() * half, Rational::zero());
+        assert!(half < third);
+        assert_eq!(Rational::new(-7, 2).floor(), -4);
+        assert_eq!(Rational::new(-7, 2).ceil(), -3);
+        assert_eq!(Rational::new(7, 2).to_string(), "7/2");
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{
+    BooleanBoundedGrid, BooleanGrid, BoundedGrid, Coordinates, Direction, Direction8, Grid,
+};
+use num_traits::PrimInt;
+use std::{hash::Hash, iter::successors};
+
+/// How a ray moves from one position to the next: towards a [`Direction`] or
+/// [`Direction8`], or by a fixed offset.
+pub trait Step<T> {
+    /// The following position, or `None` if it can't be represented.
+    fn advance(&self, pos: Coordinates<T>) -> Option<Coordinates<T>>;
+}
+
+impl<T: PrimInt> Step<T> for Direction {
+    fn advance(&self, pos: Coordinates<T>) -> Option<Coordinates<T>> {
+        use Direction::*;
+
+        let (x, y, one) = (pos.x(), pos.y(), T::one());
+
+        match self {
+            Up => y.checked_sub(&one).map(|y| Coordinates::new(x, y)),
+            Right => x.checked_add(&one).map(|x| Coordinates::new(x, y)),
+            Down => y.checked_add(&one).map(|y| Coordinates::new(x, y)),
+            Left => x.checked_sub(&one).map(|x| Coordinates::new(x, y)),
+        }
+    }
+}
+
+impl<T: PrimInt> Step<T> for Direction8 {
+    fn advance(&self, pos: Coordinates<T>) -> Option<Coordinates<T>> {
+        let pos = match self.vertical() {
+            Some(dir) => dir.advance(pos)?,
+            None => pos,
+        };
+
+        match self.horizontal() {
+            Some(dir) => dir.advance(pos),
+            None => Some(pos),
+        }
+    }
+}
+
+impl<T: PrimInt> Step<T> for Coordinates<T> {
+    fn advance(&self, pos: Coordinates<T>) -> Option<Coordinates<T>> {
+        Some(Coordinates::new(
+            pos.x().checked_add(&self.x())?,
+            pos.y().checked_add(&self.y())?,
+        ))
+    }
+}
+
+/// Positions after `from`, moving by `step`, for as long as `within` accepts
+/// them.
+fn walk<T, S, W>(from: Coordinates<T>, step: S, within: W) -> impl Iterator<Item = Coordinates<T>>
+where
+    T: Copy,
+    S: Step<T>,
+    W: Fn(Coordinates<T>) -> bool,
+{
+    successors(step.advance(from), move |&pos| step.advance(pos))
+        .take_while(move |&pos| within(pos))
+}
+
+/// Same as [`walk`], also stopping after the first position `blocks` accepts.
+fn sight<T, I, B>(ray: I, blocks: B) -> impl Iterator<Item = Coordinates<T>>
+where
+    T: Copy,
+    I: Iterator<Item = Coordinates<T>>,
+    B: Fn(Coordinates<T>) -> bool,
+{
+    let mut blocked = false;
+
+    ray.take_while(move |&pos| {
+        !blocked && {
+            blocked = blocks(pos);
+            true
+        }
+    })
+}
+
+/// Every value from `min` to `max`, both included.
+fn span<T: PrimInt>(min: T, max: T) -> impl Iterator<Item = T> {
+    successors(Some(min), move |&v| (v < max).then(|| v + T::one())).take_while(move |&v| v <= max)
+}
+
+impl<T, U> Grid<T, U>
+where
+    T: Eq + Hash + Default + PrimInt,
+    U: Clone + Default,
+{
+    /// Positions met walking from `from`, excluded, by `step` until leaving
+    /// the bounds of the grid.
+    pub fn ray<S: Step<T>>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        walk(from, step, |pos| self.within_bounds(pos))
+    }
+
+    /// Same as [`Self::ray`], also stopping after the first cell whose value
+    /// `blocks` accepts.
+    pub fn line_of_sight<S, B>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+        blocks: B,
+    ) -> impl Iterator<Item = Coordinates<T>>
+    where
+        S: Step<T>,
+        B: Fn(&U) -> bool,
+    {
+        sight(self.ray(from, step), move |pos| {
+            self.get(&pos).is_some_and(&blocks)
+        })
+    }
+
+    /// Cells of row `y` within the bounds, from left to right.
+    pub fn row(&self, y: T) -> impl Iterator<Item = (Coordinates<T>, &U)> {
+        self.bounding_box()
+            .into_iter()
+            .flat_map(move |(min, max)| span(min.x(), max.x()))
+            .filter_map(move |x| {
+                let pos = Coordinates::new(x, y);
+                self.get(&pos).map(|val| (pos, val))
+            })
+    }
+
+    /// Cells of column `x` within the bounds, from top to bottom.
+    pub fn column(&self, x: T) -> impl Iterator<Item = (Coordinates<T>, &U)> {
+        self.bounding_box()
+            .into_iter()
+            .flat_map(move |(min, max)| span(min.y(), max.y()))
+            .filter_map(move |y| {
+                let pos = Coordinates::new(x, y);
+                self.get(&pos).map(|val| (pos, val))
+            })
+    }
+}
+
+impl<T, U> BoundedGrid<T, U>
+where
+    T: Eq + Hash + Default + PrimInt,
+    U: Clone + Default,
+{
+    /// Positions met walking from `from`, excluded, by `step` until leaving
+    /// the bounds of the grid.
+    pub fn ray<S: Step<T>>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        walk(from, step, |pos| self.within_bounds(pos))
+    }
+
+    /// Same as [`Self::ray`], also stopping after the first cell whose value
+    /// `blocks` accepts.
+    pub fn line_of_sight<S, B>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+        blocks: B,
+    ) -> impl Iterator<Item = Coordinates<T>>
+    where
+        S: Step<T>,
+        B: Fn(&U) -> bool,
+    {
+        sight(self.ray(from, step), move |pos| {
+            self.get(&pos).is_some_and(&blocks)
+        })
+    }
+
+    /// Cells of row `y`, from left to right.
+    pub fn row(&self, y: T) -> impl Iterator<Item = (Coordinates<T>, &U)> {
+        let (min, max) = self.bounding_box();
+
+        span(min.x(), max.x()).filter_map(move |x| {
+            let pos = Coordinates::new(x, y);
+            self.get(&pos).map(|val| (pos, val))
+        })
+    }
+
+    /// Cells of column `x`, from top to bottom.
+    pub fn column(&self, x: T) -> impl Iterator<Item = (Coordinates<T>, &U)> {
+        let (min, max) = self.bounding_box();
+
+        span(min.y(), max.y()).filter_map(move |y| {
+            let pos = Coordinates::new(x, y);
+            self.get(&pos).map(|val| (pos, val))
+        })
+    }
+}
+
+impl<T> BooleanGrid<T>
+where
+    T: Eq + Hash + Default + PrimInt,
+{
+    /// Positions met walking from `from`, excluded, by `step` until leaving
+    /// the bounds of the grid.
+    pub fn ray<S: Step<T>>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        walk(from, step, |pos| self.within_bounds(pos))
+    }
+
+    /// Same as [`Self::ray`], also stopping after the first marked cell.
+    pub fn line_of_sight<S: Step<T>>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        sight(self.ray(from, step), |pos| self.contains(&pos))
+    }
+
+    /// Marked cells of row `y`, from left to right.
+    pub fn row(&self, y: T) -> impl Iterator<Item = Coordinates<T>> + '_ {
+        self.bounding_box()
+            .into_iter()
+            .flat_map(move |(min, max)| span(min.x(), max.x()))
+            .map(move |x| Coordinates::new(x, y))
+            .filter(|pos| self.contains(pos))
+    }
+
+    /// Marked cells of column `x`, from top to bottom.
+    pub fn column(&self, x: T) -> impl Iterator<Item = Coordinates<T>> + '_ {
+        self.bounding_box()
+            .into_iter()
+            .flat_map(move |(min, max)| span(min.y(), max.y()))
+            .map(move |y| Coordinates::new(x, y))
+            .filter(|pos| self.contains(pos))
+    }
+}
+
+impl<T> BooleanBoundedGrid<T>
+where
+    T: Eq + Hash + Default + PrimInt,
+{
+    /// Positions met walking from `from`, excluded, by `step` until leaving
+    /// the bounds of the grid.
+    pub fn ray<S: Step<T>>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        walk(from, step, |pos| self.within_bounds(pos))
+    }
+
+    /// Same as [`Self::ray`], also stopping after the first marked cell.
+    pub fn line_of_sight<S: Step<T>>(
+        &self,
+        from: Coordinates<T>,
+        step: S,
+    ) -> impl Iterator<Item = Coordinates<T>> {
+        sight(self.ray(from, step), |pos| self.contains(&pos))
+    }
+
+    /// Marked cells of row `y`, from left to right.
+    pub fn row(&self, y: T) -> impl Iterator<Item = Coordinates<T>> + '_ {
+        let (min, max) = self.bounding_box();
+
+        span(min.x(), max.x())
+            .map(move |x| Coordinates::new(x, y))
+            .filter(|pos| self.contains(pos))
+    }
+
+    /// Marked cells of column `x`, from top to bottom.
+    pub fn column(&self, x: T) -> impl Iterator<Item = Coordinates<T>> + '_ {
+        let (min, max) = self.bounding_box();
+
+        span(min.y(), max.y())
+            .map(move |y| Coordinates::new(x, y))
+            .filter(|pos| self.contains(pos))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FOREST: &str = r#"30373
+25512
+65332
+33549
+35390"#;
+
+    fn forest() -> BoundedGrid<u8, u8> {
+        BoundedGrid::parse(FOREST, |c| c.to_digit(10).map(|d| d as u8))
+    }
+
+    #[test]
+    fn ray_stops_at_bounds() {
+        let grid = forest();
+
+        assert_eq!(
+            grid.ray(Coordinates::new(2, 2), Direction::Left)
+                .collect::<Vec<_>>(),
+            vec![Coordinates::new(1, 2), Coordinates::new(0, 2)]
+        );
+        assert_eq!(grid.ray(Coordinates::new(0, 0), Direction::Up).count(), 0);
+        assert_eq!(
+            grid.ray(Coordinates::new(0, 0), Coordinates::new(2, 1))
+                .collect::<Vec<_>>(),
+            vec![Coordinates::new(2, 1), Coordinates::new(4, 2)]
+        );
+    }
+
+    #[test]
+    fn line_of_sight_includes_blocking_cell() {
+        let grid = forest();
+        let from = Coordinates::new(2, 3);
+        let height = *grid.get(&from).unwrap();
+
+        let view = |dir| grid.line_of_sight(from, dir, |&h| h >= height).count();
+
+        assert_eq!(
+            [
+                Direction::Up,
+                Direction::Left,
+                Direction::Down,
+                Direction::Right
+            ]
+            .map(view),
+            [2, 2, 1, 2]
+        );
+    }
+
+    #[test]
+    fn boolean_line_of_sight_stops_at_marked_cell() {
+        let grid = BooleanGrid::<i16>::parse("#...#..", |c| c == '#');
+
+        assert_eq!(
+            grid.line_of_sight(Coordinates::new(1, 0), Coordinates::new(1, 0))
+                .last(),
+            Some(Coordinates::new(4, 0))
+        );
+        assert_eq!(
+            grid.ray(Coordinates::new(1, 0), Direction::Right).count(),
+            3
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = forest();
+
+        assert_eq!(
+            grid.row(1).map(|(_, &h)| h).collect::<Vec<_>>(),
+            vec![2, 5, 5, 1, 2]
+        );
+        assert_eq!(
+            grid.column(4).map(|(_, &h)| h).collect::<Vec<_>>(),
+            vec![3, 2, 2, 9, 0]
+        );
+
+        let marks = BooleanBoundedGrid::<i16>::parse(".#\n##", |c| c == '#');
+        assert_eq!(marks.row(1).count(), 2);
+        assert_eq!(
+            marks.column(0).collect::<Vec<_>>(),
+            vec![Coordinates::new(0, 1)]
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{Coordinates, distances::span};
+use num_traits::PrimInt;
+
+/// An axis-aligned rectangle of cells, between two corners that are both
+/// part of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rectangle<T> {
+    min: Coordinates<T>,
+    max: Coordinates<T>,
+}
+
+impl<T: PrimInt> Rectangle<T> {
+    /// The rectangle between any two opposite corners.
+    #[must_use]
+    pub fn new(a: Coordinates<T>, b: Coordinates<T>) -> Self {
+        Self {
+            min: Coordinates::new(a.x().min(b.x()), a.y().min(b.y())),
+            max: Coordinates::new(a.x().max(b.x()), a.y().max(b.y())),
+        }
+    }
+
+    /// The rectangle of `width` by `height` cells whose top left corner is
+    /// `origin`, or `None` if either side is zero.
+    #[must_use]
+    pub fn with_size(origin: Coordinates<T>, width: T, height: T) -> Option<Self> {
+        (!width.is_zero() && !height.is_zero()).then(|| Self {
+            min: origin,
+            max: Coordinates::new(
+                origin.x() + width - T::one(),
+                origin.y() + height - T::one(),
+            ),
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn min(&self) -> Coordinates<T> {
+        self.min
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn max(&self) -> Coordinates<T> {
+        self.max
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> T {
+        self.max.x() - self.min.x() + T::one()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> T {
+        self.max.y() - self.min.y() + T::one()
+    }
+
+    /// Number of cells.
+    #[must_use]
+    #[inline]
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        pos.x() >= self.min.x()
+            && pos.x() <= self.max.x()
+            && pos.y() >= self.min.y()
+            && pos.y() <= self.max.y()
+    }
+
+    /// Cells shared by both rectangles, if any.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Coordinates::new(
+            self.min.x().max(other.min.x()),
+            self.min.y().max(other.min.y()),
+        );
+        let max = Coordinates::new(
+            self.max.x().min(other.max.x()),
+            self.max.y().min(other.max.y()),
+        );
+
+        (min.x() <= max.x() && min.y() <= max.y()).then_some(Self { min, max })
+    }
+
+    /// Smallest rectangle containing both rectangles.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: Coordinates::new(
+                self.min.x().min(other.min.x()),
+                self.min.y().min(other.min.y()),
+            ),
+            max: Coordinates::new(
+                self.max.x().max(other.max.x()),
+                self.max.y().max(other.max.y()),
+            ),
+        }
+    }
+
+    /// The top left, top right, bottom left and bottom right quarters. When
+    /// a side has an odd length, its middle row or column belongs to no
+    /// quadrant. Empty quadrants are skipped.
+    pub fn quadrants(&self) -> impl Iterator<Item = Self> {
+        let two = T::one() + T::one();
+        let halves = |min: T, size: T| {
+            let half = size / two;
+            let far = min + size - half;
+
+            [(min, half), (far, half)]
+        };
+
+        let xs = halves(self.min.x(), self.width());
+        let ys = halves(self.min.y(), self.height());
+
+        ys.into_iter().flat_map(move |(y, height)| {
+            xs.into_iter().filter_map(move |(x, width)| {
+                Self::with_size(Coordinates::new(x, y), width, height)
+            })
+        })
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Coordinates<T>> {
+        let (min, max) = (self.min, self.max);
+
+        span(min.y(), max.y())
+            .flat_map(move |y| span(min.x(), max.x()).map(move |x| Coordinates::new(x, y)))
+    }
+}
+
+impl<T: PrimInt> From<(Coordinates<T>, Coordinates<T>)> for Rectangle<T> {
+    fn from((a, b): (Coordinates<T>, Coordinates<T>)) -> Self {
+        Self::new(a, b)
+    }
+}
+
+/// A collection of possibly overlapping rectangles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RectangleSet<T> {
+    rectangles: Vec<Rectangle<T>>,
+}
+
+impl<T: PrimInt> RectangleSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { rectangles: vec![] }
+    }
+
+    pub fn insert(&mut self, rectangle: Rectangle<T>) {
+        self.rectangles.push(rectangle);
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Rectangle<T>> {
+        self.rectangles.iter()
+    }
+
+    /// Number of rectangles.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rectangles.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rectangles.is_empty()
+    }
+
+    /// Whether any rectangle contains `pos`.
+    #[must_use]
+    pub fn covers(&self, pos: &Coordinates<T>) -> bool {
+        self.rectangles.iter().any(|rect| rect.contains(pos))
+    }
+
+    /// Number of cells covered by at least one rectangle.
+    ///
+    /// Sweeps vertical strips between rectangle edges, merging the rows
+    /// covered within each strip.
+    #[must_use]
+    pub fn covered_area(&self) -> T {
+        let mut edges = self
+            .rectangles
+            .iter()
+            .flat_map(|rect| [rect.min.x(), rect.max.x() + T::one()])
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup();
+
+        edges
+            .windows(2)
+            .map(|strip| {
+                let mut rows = self
+                    .rectangles
+                    .iter()
+                    .filter(|rect| rect.min.x() <= strip[0] && rect.max.x() >= strip[0])
+                    .map(|rect| (rect.min.y(), rect.max.y() + T::one()))
+                    .collect::<Vec<_>>();
+                rows.sort_unstable();
+
+                let mut covered = T::zero();
+                let mut reached = None;
+                for (start, end) in rows {
+                    let start = reached.map_or(start, |reached: T| reached.max(start));
+                    if end > start {
+                        covered = covered + end - start;
+                        reached = Some(end);
+                    }
+                }
+
+                covered * (strip[1] - strip[0])
+            })
+            .fold(T::zero(), |total, area| total + area)
+    }
+}
+
+impl<T: PrimInt> FromIterator<Rectangle<T>> for RectangleSet<T> {
+    fn from_iter<I: IntoIterator<Item = Rectangle<T>>>(iter: I) -> Self {
+        Self {
+            rectangles: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalises_and_measures() {
+        let rect = Rectangle::new(Coordinates::new(9u32, 5), Coordinates::new(2, 3));
+
+        assert_eq!(rect.min(), Coordinates::new(2, 3));
+        assert_eq!(rect.area(), 8 * 3);
+        assert_eq!(rect.iter().count(), 24);
+        assert_eq!(rect.iter().nth(8), Some(Coordinates::new(2, 4)));
+        assert!(rect.contains(&Coordinates::new(9, 3)));
+        assert!(!rect.contains(&Coordinates::new(10, 3)));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = Rectangle::new(Coordinates::new(0i8, 0), Coordinates::new(4, 4));
+        let b = Rectangle::new(Coordinates::new(3, -2), Coordinates::new(6, 1));
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rectangle::new(
+                Coordinates::new(3, 0),
+                Coordinates::new(4, 1)
+            ))
+        );
+        assert_eq!(a.union(&b).area(), 7 * 7);
+        assert_eq!(
+            a.intersection(&Rectangle::new(
+                Coordinates::new(5, 5),
+                Coordinates::new(6, 6)
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn quadrants_skip_middle_lines() {
+        let odd = Rectangle::with_size(Coordinates::new(0i32, 0), 11, 7).unwrap();
+        let even = Rectangle::with_size(Coordinates::new(0i32, 0), 4, 1).unwrap();
+
+        assert_eq!(
+            odd.quadrants()
+                .map(|q| (q.min(), q.area()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Coordinates::new(0, 0), 15),
+                (Coordinates::new(6, 0), 15),
+                (Coordinates::new(0, 4), 15),
+                (Coordinates::new(6, 4), 15)
+            ]
+        );
+        assert_eq!(even.quadrants().count(), 0);
+    }
+
+    #[test]
+    fn covered_area_counts_overlaps_once() {
+        let set = [
+            ((1, 3), (4, 6)),
+            ((3, 1), (6, 4)),
+            ((5, 5), (6, 6)),
+            ((2, 2), (2, 2)),
+        ]
+        .into_iter()
+        .map(|(a, b)| Rectangle::new(Coordinates::from(a), Coordinates::from(b)))
+        .collect::<RectangleSet<i64>>();
+
+        let brute = Rectangle::new(Coordinates::new(0, 0), Coordinates::new(7, 7))
+            .iter()
+            .filter(|pos| set.covers(pos))
+            .count();
+
+        assert_eq!(set.covered_area(), brute as i64);
+        assert_eq!(RectangleSet::<u8>::new().covered_area(), 0);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{BooleanGrid, Coordinates, Grid, neighborhood::Neighborhood};
+use num_traits::PrimInt;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    collections::hash_map::Entry,
+    hash::Hash,
+    ops::{AddAssign, SubAssign},
+};
+
+/// A connected group of cells, as found by [`Grid::regions`] or
+/// [`BooleanGrid::regions`].
+#[derive(Debug, Clone)]
+pub struct Region<T> {
+    cells: FxHashSet<Coordinates<T>>,
+    min: Coordinates<T>,
+    max: Coordinates<T>,
+}
+
+impl<T> Region<T>
+where
+    T: Eq + Hash + PrimInt + AddAssign + SubAssign,
+{
+    fn new(cells: FxHashSet<Coordinates<T>>) -> Self {
+        let first = *cells.iter().next().unwrap();
+        let (min, max) = cells.iter().fold((first, first), |(min, max), pos| {
+            (
+                Coordinates::new(min.x().min(pos.x()), min.y().min(pos.y())),
+                Coordinates::new(max.x().max(pos.x()), max.y().max(pos.y())),
+            )
+        });
+
+        Self { cells, min, max }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        self.cells.contains(pos)
+    }
+
+    #[inline]
+    pub fn cells(&self) -> impl Iterator<Item = &Coordinates<T>> {
+        self.cells.iter()
+    }
+
+    /// Number of cells in the region.
+    #[must_use]
+    #[inline]
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges that separate the region from the outside,
+    /// including the edges around holes.
+    #[must_use]
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|pos| 4 - pos.checked_neighbors().filter(|n| self.contains(n)).count())
+            .sum()
+    }
+
+    /// Number of corners of the region's outline, which is also its number
+    /// of straight sides.
+    #[must_use]
+    pub fn corners(&self) -> usize {
+        let one = T::one();
+        // Lookups stay in range of `T`, positions that don't fit being outside.
+        let has = |x: Option<T>, y: Option<T>| match (x, y) {
+            (Some(x), Some(y)) => self.contains(&Coordinates::new(x, y)),
+            _ => false,
+        };
+
+        self.cells
+            .iter()
+            .map(|pos| {
+                let (x, y) = (Some(pos.x()), Some(pos.y()));
+                let (up, down) = (pos.y().checked_sub(&one), pos.y().checked_add(&one));
+                let (left, right) = (pos.x().checked_sub(&one), pos.x().checked_add(&one));
+
+                [
+                    (has(x, up), has(right, y), has(right, up)),
+                    (has(right, y), has(x, down), has(right, down)),
+                    (has(x, down), has(left, y), has(left, down)),
+                    (has(left, y), has(x, up), has(left, up)),
+                ]
+                .iter()
+                .filter(|&&(a, b, diagonal)| match (a, b) {
+                    (false, false) => true,
+                    (true, true) => !diagonal,
+                    _ => false,
+                })
+                .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides of the region's outline.
+    #[must_use]
+    #[inline]
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    /// Smallest and largest corner of the box enclosing the region.
+    #[must_use]
+    #[inline]
+    pub fn bounding_box(&self) -> (Coordinates<T>, Coordinates<T>) {
+        (self.min, self.max)
+    }
+}
+
+/// Every connected region of a grid, along with the region each cell belongs
+/// to.
+#[derive(Debug, Clone)]
+pub struct Regions<T> {
+    regions: Vec<Region<T>>,
+    labels: FxHashMap<Coordinates<T>, usize>,
+}
+
+impl<T> Regions<T>
+where
+    T: Eq + Hash + PrimInt + AddAssign + SubAssign,
+{
+    fn find<I, N, It>(cells: I, neighbors: N) -> Self
+    where
+        I: IntoIterator<Item = Coordinates<T>>,
+        N: Fn(Coordinates<T>) -> It,
+        It: IntoIterator<Item = Coordinates<T>>,
+    {
+        let mut regions = vec![];
+        let mut labels = FxHashMap::default();
+
+        for start in cells {
+            if labels.contains_key(&start) {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut cells = FxHashSet::from_iter([start]);
+            let mut queue = vec![start];
+            labels.insert(start, label);
+
+            while let Some(pos) = queue.pop() {
+                for neighbor in neighbors(pos) {
+                    if let Entry::Vacant(entry) = labels.entry(neighbor) {
+                        entry.insert(label);
+                        cells.insert(neighbor);
+                        queue.push(neighbor);
+                    }
+                }
+            }
+
+            regions.push(Region::new(cells));
+        }
+
+        Self { regions, labels }
+    }
+
+    /// Index of the region `pos` belongs to.
+    #[must_use]
+    #[inline]
+    pub fn label(&self, pos: &Coordinates<T>) -> Option<usize> {
+        self.labels.get(pos).copied()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get(&self, label: usize) -> Option<&Region<T>> {
+        self.regions.get(label)
+    }
+
+    /// The region `pos` belongs to.
+    #[must_use]
+    #[inline]
+    pub fn region_of(&self, pos: &Coordinates<T>) -> Option<&Region<T>> {
+        self.label(pos).and_then(|label| self.get(label))
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Region<T>> {
+        self.regions.iter()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+impl<T, U> Grid<T, U>
+where
+    T: Eq + Hash + Default + PrimInt + AddAssign + SubAssign,
+    U: Clone + Default,
+{
+    /// Splits the grid into connected regions, where two cells adjacent
+    /// through `neighborhood` belong to the same region when `same_region`
+    /// accepts their values.
+    #[must_use]
+    pub fn regions<N, P>(&self, neighborhood: &N, same_region: P) -> Regions<T>
+    where
+        N: Neighborhood<T>,
+        P: Fn(&U, &U) -> bool,
+    {
+        Regions::find(self.iter().map(|(&pos, _)| pos), |pos| {
+            let val = self.get(&pos).unwrap();
+
+            neighborhood.neighbors(pos).filter(|neighbor| {
+                self.get(neighbor)
+                    .is_some_and(|other| same_region(val, other))
+            })
+        })
+    }
+}
+
+impl<T> BooleanGrid<T>
+where
+    T: Eq + Hash + Default + PrimInt + AddAssign + SubAssign,
+{
+    /// Splits the marked cells into regions connected through `neighborhood`.
+    #[must_use]
+    pub fn regions<N: Neighborhood<T>>(&self, neighborhood: &N) -> Regions<T> {
+        Regions::find(self.iter().copied(), |pos| {
+            neighborhood
+                .neighbors(pos)
+                .filter(|neighbor| self.contains(neighbor))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EightWay, FourWay};
+
+    const GARDEN: &str = r#"AAAA
+BBCD
+BBCC
+EEEC"#;
+
+    const HOLES: &str = r#"OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO"#;
+
+    fn summary(regions: &Regions<i16>) -> Vec<(usize, usize, usize)> {
+        let mut summary = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect::<Vec<_>>();
+        summary.sort();
+
+        summary
+    }
+
+    #[test]
+    fn grid_regions_measures_each_region() {
+        let garden = Grid::<i16, char>::parse(GARDEN, Some);
+        let regions = garden.regions(&FourWay, |a, b| a == b);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(
+            summary(&regions),
+            vec![(1, 4, 4), (3, 8, 4), (4, 8, 4), (4, 10, 4), (4, 10, 8)]
+        );
+    }
+
+    #[test]
+    fn grid_regions_counts_sides_around_holes() {
+        let garden = Grid::<i16, char>::parse(HOLES, Some);
+        let regions = garden.regions(&FourWay, |a, b| a == b);
+        let outer = regions.region_of(&Coordinates::new(0, 0)).unwrap();
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(outer.area(), 21);
+        assert_eq!(outer.perimeter(), 36);
+        assert_eq!(outer.sides(), 20);
+    }
+
+    #[test]
+    fn grid_regions_labels_cells() {
+        let garden = Grid::<i16, char>::parse(GARDEN, Some);
+        let regions = garden.regions(&FourWay, |a, b| a == b);
+
+        assert_eq!(
+            regions.label(&Coordinates::new(2, 1)),
+            regions.label(&Coordinates::new(3, 3))
+        );
+        assert_ne!(
+            regions.label(&Coordinates::new(3, 1)),
+            regions.label(&Coordinates::new(3, 2))
+        );
+        assert_eq!(regions.label(&Coordinates::new(9, 9)), None);
+        assert_eq!(
+            regions
+                .region_of(&Coordinates::new(2, 2))
+                .unwrap()
+                .bounding_box(),
+            (Coordinates::new(2, 1), Coordinates::new(3, 3))
+        );
+    }
+
+    #[test]
+    fn unsigned_grid_regions_measure_cells_on_the_edges() {
+        let garden = Grid::<usize, char>::parse("AAB\nABB", Some);
+        let regions = garden.regions(&FourWay, |a, b| a == b);
+
+        let mut summary = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect::<Vec<_>>();
+        summary.sort();
+
+        assert_eq!(summary, vec![(3, 8, 6), (3, 8, 6)]);
+    }
+
+    #[test]
+    fn boolean_grid_regions_splits_marked_cells() {
+        let grid = BooleanGrid::<i16>::parse(HOLES, |c| c == 'X');
+        let regions = grid.regions(&FourWay);
+
+        assert_eq!(regions.len(), 4);
+        assert!(regions.iter().all(|r| r.area() == 1 && r.sides() == 4));
+    }
+
+    #[test]
+    fn boolean_grid_regions_with_eight_way_joins_diagonals() {
+        let grid = BooleanGrid::<i16>::parse("#..\n.#.\n..#", |c| c == '#');
+
+        assert_eq!(grid.regions(&FourWay).len(), 3);
+        assert_eq!(grid.regions(&EightWay).len(), 1);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::Coordinates;
+use num_traits::PrimInt;
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    hash::Hash,
+};
+
+type CellFormatter<'a, T> = Box<dyn Fn(Coordinates<T>) -> Option<char> + 'a>;
+
+/// Text rendering of a grid, one character per cell. Obtained through the
+/// `render` method of the grid types and printed through its `Display` impl.
+pub struct Render<'a, T> {
+    bounds: Option<(Coordinates<T>, Coordinates<T>)>,
+    cell: CellFormatter<'a, T>,
+    fill: char,
+    overlays: FxHashMap<Coordinates<T>, char>,
+}
+
+impl<'a, T> Render<'a, T>
+where
+    T: PrimInt + Hash,
+{
+    pub(crate) fn new<F>(bounds: Option<(Coordinates<T>, Coordinates<T>)>, cell: F) -> Self
+    where
+        F: Fn(Coordinates<T>) -> Option<char> + 'a,
+    {
+        Self {
+            bounds,
+            cell: Box::new(cell),
+            fill: '.',
+            overlays: FxHashMap::default(),
+        }
+    }
+
+    /// Character used for positions that have no cell in the grid. Defaults to `.`.
+    #[must_use]
+    pub fn fill(mut self, fill: char) -> Self {
+        self.fill = fill;
+
+        self
+    }
+
+    /// Draws `glyph` over every one of the given positions, for instance a
+    /// path returned by `bfs` or the set returned by `flood`. Later overlays
+    /// are drawn on top of earlier ones.
+    #[must_use]
+    pub fn overlay<I>(mut self, positions: I, glyph: char) -> Self
+    where
+        I: IntoIterator<Item = Coordinates<T>>,
+    {
+        self.overlays
+            .extend(positions.into_iter().map(|pos| (pos, glyph)));
+
+        self
+    }
+
+    fn glyph(&self, pos: Coordinates<T>) -> char {
+        self.overlays
+            .get(&pos)
+            .copied()
+            .or_else(|| (self.cell)(pos))
+            .unwrap_or(self.fill)
+    }
+}
+
+impl<T> Display for Render<'_, T>
+where
+    T: PrimInt + Hash,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+
+        let mut y = min.y();
+        loop {
+            let mut x = min.x();
+            loop {
+                f.write_char(self.glyph(Coordinates::new(x, y)))?;
+
+                if x >= max.x() {
+                    break;
+                }
+                x = x + T::one();
+            }
+
+            if y >= max.y() {
+                break;
+            }
+
+            f.write_char('\n')?;
+            y = y + T::one();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BooleanBoundedGrid, BooleanGrid, FourWay, Grid};
+
+    const MAZE: &str = r#"#####
+#S..#
+#.#E#
+#####"#;
+
+    #[test]
+    fn grid_render_round_trips() {
+        let grid = Grid::<i16, char>::parse(MAZE, Some);
+
+        assert_eq!(grid.render(|&c| c).to_string(), MAZE);
+    }
+
+    #[test]
+    fn grid_render_uses_fill_for_missing_cells() {
+        let grid = Grid::<i16, char>::parse(MAZE, |c| (c == '#').then_some(c));
+
+        assert_eq!(
+            grid.render(|&c| c).fill(' ').to_string(),
+            "#####\n#   #\n# # #\n#####"
+        );
+    }
+
+    #[test]
+    fn grid_render_draws_overlays() {
+        let (grid, [start, end]) = Grid::<i16, char>::parse_with_markers(MAZE, ['S', 'E'], Some);
+        let path = grid.bfs(
+            &FourWay,
+            start.unwrap(),
+            |pos, _| Some(pos) == end,
+            |_, (_, &c)| c != '#',
+        );
+
+        assert_eq!(
+            grid.render(|&c| c).overlay(path, 'O').to_string(),
+            "#####\n#OOO#\n#.#O#\n#####"
+        );
+    }
+
+    #[test]
+    fn boolean_grid_render_draws_marked_cells() {
+        let (grid, [start, end]) =
+            BooleanGrid::<u8>::parse_with_markers(MAZE, ['S', 'E'], |c| c == '#');
+        let path = grid.bfs(&FourWay, start.unwrap(), end.unwrap(), false);
+
+        assert_eq!(
+            grid.render('#').overlay(path, '~').to_string(),
+            "#####\n#~~~#\n#.#~#\n#####"
+        );
+    }
+
+    #[test]
+    fn boolean_bounded_grid_render_covers_bounds() {
+        let grid = BooleanBoundedGrid::<i32>::parse("..\n.#", |c| c == '#');
+
+        assert_eq!(grid.render('#').fill(' ').to_string(), "  \n #");
+    }
+}
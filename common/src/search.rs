@@ -0,0 +1,500 @@
+use crate::{Coordinates, Direction};
+use num_traits::{PrimInt, Zero};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, hash_map::Entry},
+    hash::Hash,
+    ops::{AddAssign, SubAssign},
+};
+
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+}
+
+fn build_path<S: Clone, C>(nodes: &[Node<S, C>], mut index: usize) -> Vec<S> {
+    let mut path = vec![nodes[index].state.clone()];
+
+    while let Some(parent) = nodes[index].parent {
+        path.push(nodes[parent].state.clone());
+        index = parent;
+    }
+
+    path.reverse();
+
+    path
+}
+
+/// Finds the cheapest path from `start` to any state accepted by `is_goal`.
+/// `successors` yields every state reachable from the given one, along with
+/// the cost of that move. Returns the total cost and the path, both ends
+/// included.
+pub fn dijkstra<S, C, N, I, G>(start: S, successors: N, is_goal: G) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, successors, is_goal, |_| C::zero())
+}
+
+/// Same as [`dijkstra`], but explores the states in the order suggested by
+/// `heuristic`, which must never overestimate the remaining cost to a goal.
+pub fn astar<S, C, N, I, G, H>(
+    start: S,
+    mut successors: N,
+    mut is_goal: G,
+    mut heuristic: H,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+    H: FnMut(&S) -> C,
+{
+    let mut indices = FxHashMap::from_iter([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::zero(), 0))]);
+    let mut nodes = vec![Node {
+        state: start,
+        parent: None,
+        cost: C::zero(),
+    }];
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        if cost > nodes[index].cost {
+            continue;
+        }
+
+        if is_goal(&nodes[index].state) {
+            return Some((cost, build_path(&nodes, index)));
+        }
+
+        for (next, step) in successors(&nodes[index].state) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let node = &mut nodes[*entry.get()];
+                    if node.cost <= next_cost {
+                        continue;
+                    }
+
+                    node.cost = next_cost;
+                    node.parent = Some(index);
+
+                    *entry.get()
+                }
+                Entry::Vacant(entry) => {
+                    nodes.push(Node {
+                        state: entry.key().clone(),
+                        parent: Some(index),
+                        cost: next_cost,
+                    });
+
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+
+            heap.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+/// Every cheapest path from a start state to its goals, stored as a graph of
+/// predecessors. Returned by [`dijkstra_all`].
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S, C> {
+    states: Vec<S>,
+    predecessors: Vec<Vec<usize>>,
+    settled: Vec<usize>,
+    goals: Vec<usize>,
+    cost: C,
+}
+
+impl<S, C> ShortestPaths<S, C>
+where
+    S: Clone,
+    C: Copy,
+{
+    #[must_use]
+    #[inline]
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// The goal states reached at the lowest cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.states[i])
+    }
+
+    /// Number of distinct cheapest paths, computed without enumerating them.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        let mut counts = vec![0; self.states.len()];
+
+        self.settled.iter().for_each(|&index| {
+            counts[index] = if self.predecessors[index].is_empty() {
+                1
+            } else {
+                self.predecessors[index].iter().map(|&p| counts[p]).sum()
+            };
+        });
+
+        self.goals.iter().map(|&g| counts[g]).sum()
+    }
+
+    /// Every state that lies on at least one cheapest path.
+    pub fn states(&self) -> impl Iterator<Item = &S> {
+        let mut seen = FxHashSet::from_iter(self.goals.iter().copied());
+        let mut stack = self.goals.clone();
+
+        while let Some(index) = stack.pop() {
+            stack.extend(
+                self.predecessors[index]
+                    .iter()
+                    .filter(|&&p| seen.insert(p))
+                    .copied(),
+            );
+        }
+
+        seen.into_iter().map(|i| &self.states[i])
+    }
+
+    /// Enumerates every cheapest path, both ends included. Beware that there
+    /// may be exponentially many of them, see [`Self::count`].
+    pub fn paths(&self) -> impl Iterator<Item = Vec<S>> + '_ {
+        let mut stack = self.goals.iter().map(|&g| vec![g]).collect::<Vec<_>>();
+
+        std::iter::from_fn(move || {
+            while let Some(partial) = stack.pop() {
+                let first = *partial.last().unwrap();
+
+                if self.predecessors[first].is_empty() {
+                    return Some(
+                        partial
+                            .iter()
+                            .rev()
+                            .map(|&i| self.states[i].clone())
+                            .collect(),
+                    );
+                }
+
+                self.predecessors[first].iter().for_each(|&p| {
+                    let mut partial = partial.clone();
+                    partial.push(p);
+                    stack.push(partial);
+                });
+            }
+
+            None
+        })
+    }
+}
+
+/// Same as [`dijkstra`], but keeps track of every way to reach a state at its
+/// lowest cost, so that all the cheapest paths to the goals can be inspected.
+/// Move costs must be strictly positive.
+pub fn dijkstra_all<S, C, N, I, G>(
+    start: S,
+    mut successors: N,
+    mut is_goal: G,
+) -> Option<ShortestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    let mut indices = FxHashMap::from_iter([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Reverse((C::zero(), 0))]);
+    let mut states = vec![start];
+    let mut costs = vec![C::zero()];
+    let mut predecessors = vec![vec![]];
+    let mut settled = vec![];
+    let mut goals = vec![];
+    let mut best = None;
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+
+        if cost > costs[index] {
+            continue;
+        }
+
+        settled.push(index);
+
+        if is_goal(&states[index]) {
+            best = Some(cost);
+            goals.push(index);
+            continue;
+        }
+
+        for (next, step) in successors(&states[index]) {
+            let next_cost = cost + step;
+
+            match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+
+                    if next_cost < costs[next_index] {
+                        costs[next_index] = next_cost;
+                        predecessors[next_index] = vec![index];
+                        heap.push(Reverse((next_cost, next_index)));
+                    } else if next_cost == costs[next_index] {
+                        predecessors[next_index].push(index);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    states.push(entry.key().clone());
+                    costs.push(next_cost);
+                    predecessors.push(vec![index]);
+                    heap.push(Reverse((next_cost, *entry.insert(states.len() - 1))));
+                }
+            }
+        }
+    }
+
+    best.map(|cost| ShortestPaths {
+        states,
+        predecessors,
+        settled,
+        goals,
+        cost,
+    })
+}
+
+/// Successors for `(Coordinates, Direction)` states, where a walker can either
+/// step forward onto an open position for `step`, or turn 90° on the spot for
+/// `turn`.
+pub fn step_or_turn<T, C, F>(
+    (pos, dir): (Coordinates<T>, Direction),
+    step: C,
+    turn: C,
+    is_open: F,
+) -> impl Iterator<Item = ((Coordinates<T>, Direction), C)>
+where
+    T: PrimInt + AddAssign + SubAssign,
+    C: Copy,
+    F: Fn(Coordinates<T>) -> bool,
+{
+    let forward = pos.next(dir);
+
+    [
+        is_open(forward).then_some(((forward, dir), step)),
+        Some(((pos, dir.clockwise()), turn)),
+        Some(((pos, dir.counterclockwise()), turn)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BooleanGrid, FourWay, Grid, distances::manhattan};
+
+    const MAZE: &str = r#"#######
+#S..#E#
+#.#.#.#
+#.#...#
+#######"#;
+
+    fn graph(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 7), ('c', 9), ('f', 14)],
+            'b' => vec![('c', 10), ('d', 15)],
+            'c' => vec![('d', 11), ('f', 2)],
+            'd' => vec![('e', 6)],
+            'f' => vec![('e', 9)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        assert_eq!(
+            dijkstra('a', graph, |&n| n == 'e'),
+            Some((20, vec!['a', 'c', 'f', 'e']))
+        );
+    }
+
+    #[test]
+    fn dijkstra_handles_start_being_goal() {
+        assert_eq!(dijkstra('a', graph, |&n| n == 'a'), Some((0, vec!['a'])));
+    }
+
+    #[test]
+    fn dijkstra_returns_none_when_unreachable() {
+        assert_eq!(dijkstra('b', graph, |&n| n == 'a'), None);
+    }
+
+    #[test]
+    fn astar_finds_shortest_grid_path() {
+        let (map, [start, end]) =
+            BooleanGrid::<i32>::parse_with_markers(MAZE, ['S', 'E'], |c| c != '#');
+        let (start, end) = (start.unwrap(), end.unwrap());
+
+        let (cost, path) = astar(
+            start,
+            |pos: &Coordinates<i32>| {
+                pos.neighbors()
+                    .into_iter()
+                    .filter(|next| map.contains(next))
+                    .map(|next| (next, 1))
+            },
+            |&pos| pos == end,
+            |&pos| manhattan(pos, end),
+        )
+        .unwrap();
+
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+    }
+
+    #[test]
+    fn step_or_turn_accounts_for_turns() {
+        let (map, [start, end]) =
+            BooleanGrid::<i32>::parse_with_markers(MAZE, ['S', 'E'], |c| c != '#');
+        let end = end.unwrap();
+
+        let (cost, path) = dijkstra(
+            (start.unwrap(), Direction::Right),
+            |&state| step_or_turn(state, 1, 1000, |pos| map.contains(&pos)),
+            |&(pos, _)| pos == end,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 3008);
+        assert_eq!(path.last().map(|&(_, dir)| dir), Some(Direction::Up));
+    }
+
+    #[test]
+    fn dijkstra_all_keeps_every_cheapest_path() {
+        let paths = dijkstra_all('a', graph, |&n| n == 'e').unwrap();
+
+        assert_eq!(paths.cost(), 20);
+        assert_eq!(paths.count(), 1);
+        assert_eq!(
+            paths.paths().collect::<Vec<_>>(),
+            vec![vec!['a', 'c', 'f', 'e']]
+        );
+    }
+
+    #[test]
+    fn dijkstra_all_counts_paths_on_open_grid() {
+        let size = 12;
+        let end = Coordinates::new(size, size);
+        let paths = dijkstra_all(
+            Coordinates::new(0u64, 0),
+            |pos: &Coordinates<u64>| {
+                [pos.right(), pos.down()]
+                    .into_iter()
+                    .filter(|next| next.x() <= size && next.y() <= size)
+                    .map(|next| (next, 1))
+            },
+            |&pos| pos == end,
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost(), 24);
+        assert_eq!(paths.count(), 2_704_156);
+        assert_eq!(paths.states().count(), 169);
+    }
+
+    #[test]
+    fn dijkstra_all_handles_several_goals() {
+        let (map, [start]) = BooleanGrid::<i32>::parse_with_markers(MAZE, ['S'], |c| c != '#');
+        let targets = [Coordinates::new(3, 2), Coordinates::new(2, 1)];
+
+        let paths = dijkstra_all(
+            start.unwrap(),
+            |pos: &Coordinates<i32>| {
+                pos.neighbors()
+                    .into_iter()
+                    .filter(|next| map.contains(next))
+                    .map(|next| (next, 1))
+            },
+            |pos| targets.contains(pos),
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost(), 1);
+        assert_eq!(paths.goals().collect::<Vec<_>>(), vec![&targets[1]]);
+        assert_eq!(paths.paths().count(), 1);
+    }
+
+    #[test]
+    fn dijkstra_all_collects_states_on_diamond() {
+        let paths = dijkstra_all(
+            0,
+            |&n: &u8| match n {
+                0 => vec![(1, 1), (2, 1), (3, 5)],
+                1 | 2 => vec![(4, 1)],
+                3 => vec![(4, 1)],
+                _ => vec![],
+            },
+            |&n| n == 4,
+        )
+        .unwrap();
+
+        let mut states = paths.states().copied().collect::<Vec<_>>();
+        states.sort();
+
+        let mut all = paths.paths().collect::<Vec<_>>();
+        all.sort();
+
+        assert_eq!(paths.cost(), 2);
+        assert_eq!(paths.count(), 2);
+        assert_eq!(states, vec![0, 1, 2, 4]);
+        assert_eq!(all, vec![vec![0, 1, 4], vec![0, 2, 4]]);
+    }
+
+    #[test]
+    fn grid_dijkstra_uses_cell_costs() {
+        let grid = Grid::<i8, u32>::parse("131\n191\n111", |c| c.to_digit(10));
+
+        let (cost, path) = grid
+            .dijkstra(
+                &FourWay,
+                Coordinates::new(0, 0),
+                |pos, _| pos == Coordinates::new(2, 2),
+                |_, (_, &weight)| Some(weight),
+            )
+            .unwrap();
+
+        assert_eq!(cost, 4);
+        assert_eq!(path.len(), 5);
+    }
+
+    #[test]
+    fn grid_dijkstra_all_finds_tied_paths() {
+        let grid = Grid::<i8, u32>::parse("111\n191\n111", |c| c.to_digit(10));
+
+        let paths = grid
+            .dijkstra_all(
+                &FourWay,
+                Coordinates::new(0, 0),
+                |pos, _| pos == Coordinates::new(2, 2),
+                |_, (_, &weight)| Some(weight),
+            )
+            .unwrap();
+
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.count(), 2);
+        assert_eq!(paths.states().count(), 8);
+    }
+}
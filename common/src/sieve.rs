@@ -0,0 +1,134 @@
+/// Whether each number from `0` to `n` is prime.
+pub fn prime_sieve(n: usize) -> Vec<bool> {
+    let mut is_prime = vec![true; n + 1];
+    is_prime.iter_mut().take(2).for_each(|p| *p = false);
+
+    let mut i = 2;
+    while i * i <= n {
+        if is_prime[i] {
+            (i * i..=n).step_by(i).for_each(|j| is_prime[j] = false);
+        }
+        i += 1;
+    }
+
+    is_prime
+}
+
+/// Every prime up to `n`, in increasing order.
+pub fn primes(n: usize) -> Vec<usize> {
+    prime_sieve(n)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, is_prime)| is_prime.then_some(i))
+        .collect()
+}
+
+/// The smallest prime factor of each number from `0` to `n`, `0` and `1`
+/// mapping to themselves.
+pub fn smallest_prime_factors(n: usize) -> Vec<usize> {
+    let mut spf = (0..=n).collect::<Vec<_>>();
+
+    let mut i = 2;
+    while i * i <= n {
+        if spf[i] == i {
+            (i * i..=n).step_by(i).for_each(|j| {
+                if spf[j] == j {
+                    spf[j] = i;
+                }
+            });
+        }
+        i += 1;
+    }
+
+    spf
+}
+
+/// Prime factors of `n` with their exponents, in increasing order, using a
+/// table from [`smallest_prime_factors`] covering `n`.
+pub fn prime_factors(mut n: usize, spf: &[usize]) -> Vec<(usize, u32)> {
+    let mut factors: Vec<(usize, u32)> = vec![];
+
+    while n > 1 {
+        let p = spf[n];
+        match factors.last_mut() {
+            Some((last, exp)) if *last == p => *exp += 1,
+            _ => factors.push((p, 1)),
+        }
+        n /= p;
+    }
+
+    factors
+}
+
+/// Number of divisors of each number from `0` to `n`, `0` having none.
+pub fn divisor_counts(n: usize) -> Vec<u32> {
+    let mut counts = vec![0; n + 1];
+
+    for d in 1..=n {
+        (d..n + 1).step_by(d).for_each(|m| counts[m] += 1);
+    }
+
+    counts
+}
+
+/// Sum of the divisors of each number from `0` to `n`.
+pub fn divisor_sums(n: usize) -> Vec<u64> {
+    divisor_sums_limited(n, usize::MAX)
+}
+
+/// Same as [`divisor_sums`], each divisor `d` only counting towards its
+/// first `k` multiples `d, 2d, ..., kd`.
+pub fn divisor_sums_limited(n: usize, k: usize) -> Vec<u64> {
+    let mut sums = vec![0; n + 1];
+
+    for d in 1..=n {
+        let end = n.min(d.saturating_mul(k)) + 1;
+        (d..end).step_by(d).for_each(|m| sums[m] += d as u64);
+    }
+
+    sums
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::factors;
+
+    #[test]
+    fn primes_and_factorization() {
+        let spf = smallest_prime_factors(1_000);
+        let is_prime = prime_sieve(1_000);
+
+        assert_eq!(primes(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes(1).len(), 0);
+        assert_eq!(prime_factors(360, &spf), vec![(2, 3), (3, 2), (5, 1)]);
+
+        for n in 2..=1_000 {
+            let product = prime_factors(n, &spf)
+                .into_iter()
+                .map(|(p, e)| p.pow(e))
+                .product::<usize>();
+
+            assert_eq!(product, n);
+            assert_eq!(is_prime[n], spf[n] == n);
+        }
+    }
+
+    #[test]
+    fn divisor_tables_match_trial_division() {
+        let counts = divisor_counts(1_000);
+        let sums = divisor_sums(1_000);
+        let limited = divisor_sums_limited(1_000, 5);
+
+        for n in 1..=1_000u64 {
+            let divisors = factors(n);
+
+            assert_eq!(counts[n as usize] as usize, divisors.len());
+            assert_eq!(sums[n as usize], divisors.iter().sum::<u64>());
+            assert_eq!(
+                limited[n as usize],
+                divisors.iter().filter(|&&d| n / d <= 5).sum::<u64>()
+            );
+        }
+    }
+}
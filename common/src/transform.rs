@@ -0,0 +1,464 @@
+use crate::{BooleanBoundedGrid, BooleanGrid, BoundedGrid, Coordinates, Grid};
+use num_traits::PrimInt;
+use std::{hash::Hash, iter::successors};
+
+/// One of the eight symmetries of a rectangle. Rotations are clockwise on
+/// screen, with `y` growing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Transform {
+    #[default]
+    Identity,
+    RotateClockwise,
+    Rotate180,
+    RotateCounterclockwise,
+    Transpose,
+    FlipHorizontal,
+    FlipVertical,
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::RotateClockwise,
+        Transform::Rotate180,
+        Transform::RotateCounterclockwise,
+        Transform::Transpose,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::AntiTranspose,
+    ];
+
+    #[inline]
+    #[must_use]
+    pub fn swaps_axes(&self) -> bool {
+        use Transform::*;
+
+        matches!(
+            self,
+            RotateClockwise | RotateCounterclockwise | Transpose | AntiTranspose
+        )
+    }
+
+    /// Moves `pos`, which must lie within `min..=max`, to where it ends up
+    /// once the rectangle is transformed. The result keeps `min` as its
+    /// top-left corner.
+    #[must_use]
+    pub fn apply<T: PrimInt>(
+        &self,
+        pos: Coordinates<T>,
+        min: Coordinates<T>,
+        max: Coordinates<T>,
+    ) -> Coordinates<T> {
+        use Transform::*;
+
+        let (x, y) = (pos.x() - min.x(), pos.y() - min.y());
+        let (w, h) = (max.x() - min.x(), max.y() - min.y());
+
+        let (x, y) = match self {
+            Identity => (x, y),
+            RotateClockwise => (h - y, x),
+            Rotate180 => (w - x, h - y),
+            RotateCounterclockwise => (y, w - x),
+            Transpose => (y, x),
+            FlipHorizontal => (w - x, y),
+            FlipVertical => (x, h - y),
+            AntiTranspose => (h - y, w - x),
+        };
+
+        Coordinates::new(min.x() + x, min.y() + y)
+    }
+
+    /// Bottom-right corner of the transformed `min..=max` rectangle.
+    #[must_use]
+    pub fn apply_max<T: PrimInt>(
+        &self,
+        min: Coordinates<T>,
+        max: Coordinates<T>,
+    ) -> Coordinates<T> {
+        if self.swaps_axes() {
+            Coordinates::new(min.x() + (max.y() - min.y()), min.y() + (max.x() - min.x()))
+        } else {
+            max
+        }
+    }
+}
+
+fn positions<T: PrimInt>(
+    min: Coordinates<T>,
+    max: Coordinates<T>,
+) -> impl Iterator<Item = Coordinates<T>> {
+    let step = |to: T| move |&n: &T| (n < to).then(|| n + T::one());
+
+    successors(Some(min.y()), step(max.y())).flat_map(move |y| {
+        successors(Some(min.x()), step(max.x())).map(move |x| Coordinates::new(x, y))
+    })
+}
+
+impl<T, U> Grid<T, U>
+where
+    T: Eq + Hash + Default + PrimInt,
+    U: Clone + Default,
+{
+    /// Copy of the grid with its bounding box transformed in place.
+    #[must_use]
+    pub fn transform(&self, transform: Transform) -> Self {
+        let mut grid = Self::with_capacity(self.len());
+
+        if let Some((min, max)) = self.bounding_box() {
+            self.iter().for_each(|(&pos, val)| {
+                grid.store(transform.apply(pos, min, max), val.clone());
+            });
+        }
+
+        grid
+    }
+
+    /// All eight rotations and reflections of the grid.
+    pub fn symmetries(&self) -> impl Iterator<Item = (Transform, Self)> + '_ {
+        Transform::ALL.into_iter().map(|t| (t, self.transform(t)))
+    }
+
+    /// View of the `min..=max` rectangle of the grid, addressed relative to
+    /// `min`.
+    #[must_use]
+    pub fn window(&self, min: Coordinates<T>, max: Coordinates<T>) -> GridWindow<'_, T, U> {
+        GridWindow {
+            grid: self,
+            min,
+            max,
+        }
+    }
+}
+
+impl<T> BooleanGrid<T>
+where
+    T: Eq + Hash + Default + PrimInt,
+{
+    /// Copy of the grid with its bounding box transformed in place.
+    #[must_use]
+    pub fn transform(&self, transform: Transform) -> Self {
+        let mut grid = Self::new();
+
+        if let Some((min, max)) = self.bounding_box() {
+            self.iter()
+                .for_each(|&pos| grid.mark(transform.apply(pos, min, max)));
+        }
+
+        grid
+    }
+
+    /// All eight rotations and reflections of the grid.
+    pub fn symmetries(&self) -> impl Iterator<Item = (Transform, Self)> + '_ {
+        Transform::ALL.into_iter().map(|t| (t, self.transform(t)))
+    }
+
+    /// View of the `min..=max` rectangle of the grid, addressed relative to
+    /// `min`.
+    #[must_use]
+    pub fn window(&self, min: Coordinates<T>, max: Coordinates<T>) -> BooleanGridWindow<'_, T> {
+        BooleanGridWindow {
+            grid: self,
+            min,
+            max,
+        }
+    }
+}
+
+impl<T, U> BoundedGrid<T, U>
+where
+    T: Eq + Hash + Default + PrimInt,
+    U: Clone + Default,
+{
+    /// Copy of the grid with its bounds transformed in place.
+    #[must_use]
+    pub fn transform(&self, transform: Transform) -> Self {
+        let (min, max) = self.bounding_box();
+        let mut grid = Self::new(min, transform.apply_max(min, max));
+
+        self.iter().for_each(|(&pos, val)| {
+            grid.store(transform.apply(pos, min, max), val.clone());
+        });
+
+        grid
+    }
+
+    /// All eight rotations and reflections of the grid.
+    pub fn symmetries(&self) -> impl Iterator<Item = (Transform, Self)> + '_ {
+        Transform::ALL.into_iter().map(|t| (t, self.transform(t)))
+    }
+}
+
+impl<T> BooleanBoundedGrid<T>
+where
+    T: Eq + Hash + Default + PrimInt,
+{
+    /// Copy of the grid with its bounds transformed in place.
+    #[must_use]
+    pub fn transform(&self, transform: Transform) -> Self {
+        let (min, max) = self.bounding_box();
+        let mut grid = Self::new(min, transform.apply_max(min, max));
+
+        self.iter().for_each(|&pos| {
+            grid.mark(transform.apply(pos, min, max));
+        });
+
+        grid
+    }
+
+    /// All eight rotations and reflections of the grid.
+    pub fn symmetries(&self) -> impl Iterator<Item = (Transform, Self)> + '_ {
+        Transform::ALL.into_iter().map(|t| (t, self.transform(t)))
+    }
+}
+
+/// Rectangular view into a [`Grid`]. Positions are relative to the top-left
+/// corner of the window.
+#[derive(Debug, Clone, Copy)]
+pub struct GridWindow<'a, T, U> {
+    grid: &'a Grid<T, U>,
+    min: Coordinates<T>,
+    max: Coordinates<T>,
+}
+
+impl<'a, T, U> GridWindow<'a, T, U>
+where
+    T: Eq + Hash + Default + PrimInt,
+    U: Clone + Default,
+{
+    #[must_use]
+    pub fn get(&self, pos: &Coordinates<T>) -> Option<&'a U> {
+        self.within_bounds(*pos)
+            .then(|| self.grid.get(&(self.min + *pos)))
+            .flatten()
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        self.get(pos).is_some()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> T {
+        self.max.x() - self.min.x() + T::one()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> T {
+        self.max.y() - self.min.y() + T::one()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn within_bounds(&self, pos: Coordinates<T>) -> bool {
+        pos.x() >= T::zero()
+            && pos.y() >= T::zero()
+            && pos.x() < self.width()
+            && pos.y() < self.height()
+    }
+
+    /// Cells of the window in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates<T>, &'a U)> + '_ {
+        positions(self.min, self.max)
+            .filter_map(|pos| self.grid.get(&pos).map(|val| (pos - self.min, val)))
+    }
+
+    /// Copies the window into a grid of its own.
+    #[must_use]
+    pub fn to_grid(&self) -> BoundedGrid<T, U> {
+        let zero = Coordinates::new(T::zero(), T::zero());
+        let mut grid = BoundedGrid::new(zero, self.max - self.min);
+
+        self.iter()
+            .for_each(|(pos, val)| _ = grid.store(pos, val.clone()));
+
+        grid
+    }
+}
+
+/// Rectangular view into a [`BooleanGrid`]. Positions are relative to the
+/// top-left corner of the window.
+#[derive(Debug, Clone, Copy)]
+pub struct BooleanGridWindow<'a, T> {
+    grid: &'a BooleanGrid<T>,
+    min: Coordinates<T>,
+    max: Coordinates<T>,
+}
+
+impl<T> BooleanGridWindow<'_, T>
+where
+    T: Eq + Hash + Default + PrimInt,
+{
+    #[must_use]
+    pub fn contains(&self, pos: &Coordinates<T>) -> bool {
+        self.within_bounds(*pos) && self.grid.contains(&(self.min + *pos))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn width(&self) -> T {
+        self.max.x() - self.min.x() + T::one()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> T {
+        self.max.y() - self.min.y() + T::one()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn within_bounds(&self, pos: Coordinates<T>) -> bool {
+        pos.x() >= T::zero()
+            && pos.y() >= T::zero()
+            && pos.x() < self.width()
+            && pos.y() < self.height()
+    }
+
+    /// Marked positions of the window in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = Coordinates<T>> + '_ {
+        positions(self.min, self.max)
+            .filter(|pos| self.grid.contains(pos))
+            .map(|pos| pos - self.min)
+    }
+
+    /// Copies the window into a grid of its own.
+    #[must_use]
+    pub fn to_grid(&self) -> BooleanBoundedGrid<T> {
+        let zero = Coordinates::new(T::zero(), T::zero());
+        let mut grid = BooleanBoundedGrid::new(zero, self.max - self.min);
+
+        self.iter().for_each(|pos| _ = grid.mark(pos));
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SHAPE: &str = "#..\n###";
+
+    fn render(grid: &BooleanGrid<u8>) -> String {
+        grid.render('#').to_string()
+    }
+
+    #[test]
+    fn boolean_grid_rotates() {
+        let grid = BooleanGrid::<u8>::parse(SHAPE, |c| c == '#');
+
+        assert_eq!(
+            render(&grid.transform(Transform::RotateClockwise)),
+            "##\n#.\n#."
+        );
+        assert_eq!(render(&grid.transform(Transform::Rotate180)), "###\n..#");
+        assert_eq!(
+            render(&grid.transform(Transform::RotateCounterclockwise)),
+            ".#\n.#\n##"
+        );
+    }
+
+    #[test]
+    fn boolean_grid_reflects() {
+        let grid = BooleanGrid::<u8>::parse(SHAPE, |c| c == '#');
+
+        assert_eq!(render(&grid.transform(Transform::Transpose)), "##\n.#\n.#");
+        assert_eq!(
+            render(&grid.transform(Transform::FlipHorizontal)),
+            "..#\n###"
+        );
+        assert_eq!(render(&grid.transform(Transform::FlipVertical)), "###\n#..");
+        assert_eq!(
+            render(&grid.transform(Transform::AntiTranspose)),
+            "#.\n#.\n##"
+        );
+    }
+
+    #[test]
+    fn grid_transform_keeps_top_left_corner() {
+        let mut grid = Grid::<i32, char>::new();
+        grid.store(Coordinates::new(-3, 5), 'a');
+        grid.store(Coordinates::new(-1, 6), 'b');
+
+        let rotated = grid.transform(Transform::RotateClockwise);
+
+        assert_eq!(
+            rotated.bounding_box(),
+            Some((Coordinates::new(-3, 5), Coordinates::new(-2, 7)))
+        );
+        assert_eq!(rotated.get(&Coordinates::new(-2, 5)), Some(&'a'));
+        assert_eq!(rotated.get(&Coordinates::new(-3, 7)), Some(&'b'));
+    }
+
+    #[test]
+    fn symmetries_are_distinct_for_asymmetric_shape() {
+        let grid = BooleanGrid::<u8>::parse(SHAPE, |c| c == '#');
+        let mut rendered = grid
+            .symmetries()
+            .map(|(_, g)| render(&g))
+            .collect::<Vec<_>>();
+
+        rendered.sort();
+        rendered.dedup();
+
+        assert_eq!(rendered.len(), 8);
+    }
+
+    #[test]
+    fn transforms_compose_like_rotations() {
+        let grid = Grid::<u8, char>::parse("abc\ndef", Some);
+        let twice = grid
+            .transform(Transform::RotateClockwise)
+            .transform(Transform::RotateClockwise);
+
+        assert_eq!(
+            twice.render(|&c| c).to_string(),
+            grid.transform(Transform::Rotate180)
+                .render(|&c| c)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn bounded_grid_transform_swaps_bounds() {
+        let grid = BooleanBoundedGrid::<u8>::parse("#...\n....", |c| c == '#');
+        let rotated = grid.transform(Transform::RotateClockwise);
+
+        assert_eq!(rotated.width(), 2);
+        assert_eq!(rotated.height(), 4);
+        assert_eq!(rotated.render('#').to_string(), ".#\n..\n..\n..");
+    }
+
+    #[test]
+    fn grid_window_uses_relative_positions() {
+        let grid = Grid::<u8, char>::parse("abcd\nefgh\nijkl", Some);
+        let window = grid.window(Coordinates::new(1, 1), Coordinates::new(2, 2));
+
+        assert_eq!(window.get(&Coordinates::new(0, 0)), Some(&'f'));
+        assert_eq!(window.get(&Coordinates::new(1, 1)), Some(&'k'));
+        assert_eq!(window.get(&Coordinates::new(2, 0)), None);
+        assert!(!window.within_bounds(Coordinates::new(1, 2)));
+        assert_eq!(window.iter().map(|(_, &c)| c).collect::<String>(), "fgjk");
+        assert_eq!(window.to_grid().render(|&c| c).to_string(), "fg\njk");
+    }
+
+    #[test]
+    fn boolean_grid_window_iterates_marked_cells() {
+        let grid = BooleanGrid::<u8>::parse("#..#\n.##.\n#..#", |c| c == '#');
+        let window = grid.window(Coordinates::new(1, 0), Coordinates::new(3, 1));
+
+        assert_eq!(
+            window.iter().collect::<Vec<_>>(),
+            vec![
+                Coordinates::new(2, 0),
+                Coordinates::new(0, 1),
+                Coordinates::new(1, 1)
+            ]
+        );
+        assert!(!window.contains(&Coordinates::new(3, 0)));
+        assert_eq!(window.to_grid().render('#').to_string(), "..#\n##.");
+    }
+}